@@ -0,0 +1,184 @@
+use crate::days::Day;
+use common::error::ParseError;
+use std::time::{Duration, Instant};
+
+/// What one implementation of a part answered, and how long it took.
+#[derive(Debug)]
+pub struct Outcome {
+    pub name: String,
+    /// The answer, or why the implementation couldn't give one.
+    pub answer: Result<String, String>,
+    pub time: Duration,
+}
+
+/// The main solution of a part next to every variant registered for it.
+#[derive(Debug)]
+pub struct PartCheck {
+    pub part: u8,
+    pub solution: Outcome,
+    pub variants: Vec<Outcome>,
+}
+
+impl PartCheck {
+    /// Whether every variant gave the same answer as the main solution.
+    pub fn agree(&self) -> bool {
+        self.variants
+            .iter()
+            .all(|variant| variant.answer.is_ok() && variant.answer == self.solution.answer)
+    }
+}
+
+/// Solves `input` with the main solution of `day`, then with each of its variants, one part at
+/// a time. Parts without variants are left out. The timing of the main solution includes
+/// parsing, like the variants do.
+pub fn check(day: &Day, input: &str) -> Result<Vec<PartCheck>, ParseError> {
+    let solved = (day.solve)(input, &[1, 2])?;
+    Ok(solved
+        .into_iter()
+        .filter(|solved| day.variants.iter().any(|v| v.part == solved.part))
+        .map(|solved| PartCheck {
+            part: solved.part,
+            variants: day
+                .variants
+                .iter()
+                .filter(|variant| variant.part == solved.part)
+                .map(|variant| {
+                    let start = Instant::now();
+                    let answer = (variant.solve)(input).map_err(|err| err.to_string());
+                    Outcome {
+                        name: variant.name.to_string(),
+                        answer,
+                        time: start.elapsed(),
+                    }
+                })
+                .collect(),
+            solution: Outcome {
+                name: "solution".to_string(),
+                answer: Ok(solved.answer),
+                time: solved.parse_time + solved.solve_time,
+            },
+        })
+        .collect())
+}
+
+/// Formats the checks of a day as one aligned block per part.
+pub fn report(day: &Day, checks: &[PartCheck]) -> String {
+    let name_width = checks
+        .iter()
+        .flat_map(|check| check.variants.iter().map(|variant| variant.name.len()))
+        .chain(std::iter::once("solution".len()))
+        .max()
+        .unwrap_or(0);
+    let answer_width = checks
+        .iter()
+        .flat_map(|check| std::iter::once(&check.solution).chain(&check.variants))
+        .map(|outcome| outcome.answer.as_ref().map_or(0, String::len))
+        .max()
+        .unwrap_or(0);
+    let line = |outcome: &Outcome, verdict: &str| {
+        format!(
+            "  {:<name_width$}  {:>answer_width$}  {:>12}{}\n",
+            outcome.name,
+            outcome.answer.as_deref().unwrap_or("-"),
+            format!("{:.2?}", outcome.time),
+            verdict,
+            name_width = name_width,
+            answer_width = answer_width,
+        )
+    };
+    let mut report = String::new();
+    for check in checks {
+        report.push_str(&format!("{} part {}\n", day.id(), check.part));
+        report.push_str(&line(&check.solution, ""));
+        for variant in &check.variants {
+            let verdict = match &variant.answer {
+                Ok(answer) if Ok(answer) == check.solution.answer.as_ref() => {
+                    "  agrees".to_string()
+                }
+                Ok(_) => "  DISAGREES".to_string(),
+                Err(err) => format!("  FAILED: {}", err),
+            };
+            report.push_str(&line(variant, &verdict));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+const TEST_DAY: Day = Day {
+    year: 2020,
+    number: 1,
+    title: "Report Repair",
+    solve: common::solve::<day1::ExpenseReport>,
+    bench: common::bench::bench_solution::<day1::ExpenseReport>,
+    profile: common::memory::profile_solution::<day1::ExpenseReport>,
+    variants: &[
+        crate::days::Variant {
+            name: "day1_borrow",
+            part: 1,
+            solve: |input| {
+                day1::day1_borrow(&input.lines().collect::<Vec<_>>(), 2)
+                    .map(|a| common::Found(a).to_string())
+            },
+        },
+        crate::days::Variant {
+            name: "off_by_one",
+            part: 1,
+            solve: |input| {
+                day1::day1_borrow(&input.lines().collect::<Vec<_>>(), 2)
+                    .map(|a| common::Found(a.map(|a| a + 1)).to_string())
+            },
+        },
+        crate::days::Variant {
+            name: "always_fails",
+            part: 1,
+            solve: |_| {
+                common::error::parse_number::<i32>("x", common::error::Location::new(1, 1))
+                    .map(|a| a.to_string())
+            },
+        },
+    ],
+};
+
+#[test]
+fn test_check_compares_variants_with_the_solution() {
+    let checks = check(&TEST_DAY, "1721\n979\n366\n299\n675\n1456").unwrap();
+    assert_eq!(checks.len(), 1, "part 2 has no variants");
+
+    let check = &checks[0];
+    assert_eq!(check.part, 1);
+    assert_eq!(check.solution.answer, Ok("514579".to_string()));
+    let answers: Vec<&Result<String, String>> = check
+        .variants
+        .iter()
+        .map(|variant| &variant.answer)
+        .collect();
+    assert_eq!(
+        answers,
+        vec![
+            &Ok("514579".to_string()),
+            &Ok("514580".to_string()),
+            &Err("line 1, column 1: invalid number \"x\"".to_string())
+        ]
+    );
+    assert!(!check.agree());
+
+    let report = report(&TEST_DAY, &checks);
+    assert!(report.starts_with("day 1 part 1\n  solution    "));
+    assert!(report.contains("agrees\n"));
+    assert!(report.contains("DISAGREES\n"));
+    assert!(report.contains("FAILED: line 1, column 1"));
+}
+
+#[test]
+fn test_check_agreeing_variants() {
+    let input = "1721\n979\n366\n299\n675\n1456";
+    let checks = check(
+        crate::days::find(common::puzzle::PuzzleId::new(2020, 1)).unwrap(),
+        input,
+    )
+    .unwrap();
+    assert_eq!(checks.len(), 2);
+    assert!(checks.iter().all(PartCheck::agree));
+    assert!(check(&TEST_DAY, "1721\nabc").is_err());
+}
@@ -0,0 +1,670 @@
+use crate::generate::GenConfig;
+use common::bench::BenchConfig;
+use common::input::Source;
+use common::output::Format;
+use common::puzzle::{self, PuzzleId};
+use std::time::Duration;
+
+pub const USAGE: &str = "usage:
+    aoc list                      list every available day
+    aoc run <day|all> [--part N] [--format human|json|tsv] [--trace FILE]
+                                  solve one day (or all of them), optionally only part N;
+                                  `--trace` times reading, parsing and each part in FILE
+    aoc bench <day|all> [--warmup N] [--samples N] [--output FILE]
+                                  benchmark parsing, both parts and their variants
+    aoc accept <day|all> [--part N]
+                                  record the current answers as the known-good ones
+    aoc verify [day|all]          re-run days and flag answers that changed since accepted
+    aoc summary [--jobs N]        solve every part of every day in parallel and print one table
+    aoc alloc <day|all>           count allocations, bytes and peak memory of each phase; needs
+                                  a build with `--features alloc-profile`
+    aoc check <day|all>           run every implementation of each part and check they agree
+    aoc fetch <day|all>           download inputs that aren't cached in dayN/input.txt yet
+    aoc watch <day> [--interval MS]
+                                  re-run the tests and solution of a day when its sources or
+                                  input change, showing how the answers changed
+    aoc new-day <day> [--title TEXT]
+                                  create the crate of a new day from a template and register
+                                  it in the workspace and the runner
+    aoc repl [day] [--input FILE]
+                                  explore the input of a day interactively, `help` inside lists
+                                  what can be asked about it
+    aoc submit <day> [--part N] [--answer TEXT]
+                                  submit an answer, the computed one by default, to the first
+                                  part without an accepted answer (or part N); right answers
+                                  are accepted and wrong ones are never submitted again
+    aoc gen <day> [--size N] [--ratio PERCENT] [--seed N] [--output FILE] [--answers FILE]
+                                  generate a random input of any size with known answers;
+                                  see the README for what size and ratio mean for each day
+
+A day is `N` for 2020, or `YEAR/N` for another year. `all` selects every day of every year,
+`YEAR/all` every day of one year.
+
+options:
+    --input FILE                  use FILE (`-` for stdin) as the input of the (single)
+                                  selected day; `run` accepts it more than once
+
+By default a day's input is $AOC_INPUT_DIR/dayN.txt or else dayN/input.txt, with `YEAR/` in
+front of both outside of 2020.
+`fetch` and `submit` read the session token from $AOC_SESSION or `session: TOKEN` in
+$AOC_CONFIG (~/.config/aoc/config by default), and the website from $AOC_BASE_URL or
+`base_url: URL`.";
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Year(u16),
+    Day(PuzzleId),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List,
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        inputs: Vec<Source>,
+        format: Format,
+        trace: Option<String>,
+    },
+    Bench {
+        selection: Selection,
+        config: BenchConfig,
+        output: Option<String>,
+        input: Option<Source>,
+    },
+    Accept {
+        selection: Selection,
+        part: Option<u8>,
+    },
+    Verify {
+        selection: Selection,
+    },
+    Alloc {
+        selection: Selection,
+        input: Option<Source>,
+    },
+    Check {
+        selection: Selection,
+        input: Option<Source>,
+    },
+    Fetch {
+        selection: Selection,
+    },
+    Watch {
+        day: PuzzleId,
+        interval: Duration,
+    },
+    Summary {
+        jobs: Option<usize>,
+    },
+    NewDay {
+        day: PuzzleId,
+        title: Option<String>,
+    },
+    Repl {
+        day: Option<PuzzleId>,
+        input: Option<Source>,
+    },
+    Gen {
+        day: PuzzleId,
+        config: GenConfig,
+        output: Option<String>,
+        answers: Option<String>,
+    },
+    Submit {
+        day: PuzzleId,
+        part: Option<u8>,
+        answer: Option<String>,
+    },
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+    match args.next() {
+        Some("list") => match args.next() {
+            None => Ok(Command::List),
+            Some(unexpected) => Err(format!("unexpected argument: {}", unexpected)),
+        },
+        Some("run") => {
+            let selection = parse_selection(args.next())?;
+            let mut part = None;
+            let mut inputs = Vec::new();
+            let mut format = Format::Human;
+            let mut trace = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--format" | "-f" => format = args.next().ok_or("missing format")?.parse()?,
+                    "--trace" => trace = Some(args.next().ok_or("missing trace file")?.to_string()),
+                    "--part" | "-p" => part = Some(parse_part(args.next())?),
+                    "--input" | "-i" => inputs.push(parse_input(&selection, args.next())?),
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Run {
+                selection,
+                part,
+                inputs,
+                format,
+                trace,
+            })
+        }
+        Some("bench") => {
+            let selection = parse_selection(args.next())?;
+            let mut config = BenchConfig::default();
+            let mut output = None;
+            let mut input = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--input" | "-i" => input = Some(parse_input(&selection, args.next())?),
+                    "--warmup" => config.warmup = parse_count(args.next())?,
+                    "--samples" => config.samples = parse_count(args.next())?,
+                    "--output" | "-o" => {
+                        output = Some(args.next().ok_or("missing output file")?.to_string())
+                    }
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            if config.samples == 0 {
+                return Err("at least one sample is needed".to_string());
+            }
+            Ok(Command::Bench {
+                selection,
+                config,
+                output,
+                input,
+            })
+        }
+        Some("accept") => {
+            let selection = parse_selection(args.next())?;
+            let mut part = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--part" | "-p" => part = Some(parse_part(args.next())?),
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Accept { selection, part })
+        }
+        Some("verify") => {
+            let selection = match args.next() {
+                None => Selection::All,
+                day => parse_selection(day)?,
+            };
+            match args.next() {
+                None => Ok(Command::Verify { selection }),
+                Some(unexpected) => Err(format!("unexpected argument: {}", unexpected)),
+            }
+        }
+        Some(command @ ("alloc" | "check")) => {
+            let selection = parse_selection(args.next())?;
+            let mut input = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--input" | "-i" => input = Some(parse_input(&selection, args.next())?),
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            if command == "alloc" {
+                Ok(Command::Alloc { selection, input })
+            } else {
+                Ok(Command::Check { selection, input })
+            }
+        }
+        Some("fetch") => {
+            let selection = parse_selection(args.next())?;
+            match args.next() {
+                None => Ok(Command::Fetch { selection }),
+                Some(unexpected) => Err(format!("unexpected argument: {}", unexpected)),
+            }
+        }
+        Some("summary") => {
+            let mut jobs = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--jobs" | "-j" => jobs = Some(parse_count(args.next())?),
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Summary { jobs })
+        }
+        Some("watch") => {
+            let day = match parse_selection(args.next())? {
+                Selection::Day(day) => day,
+                _ => return Err("watch only works with a single day".to_string()),
+            };
+            let mut interval = Duration::from_millis(500);
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--interval" => {
+                        interval = Duration::from_millis(parse_count(args.next())?.max(1) as u64)
+                    }
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Watch { day, interval })
+        }
+        Some("new-day") => {
+            let day = match parse_selection(args.next())? {
+                Selection::Day(day) if (1..=25).contains(&day.day) => day,
+                _ => return Err("new-day needs a day from 1 to 25".to_string()),
+            };
+            let mut title = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--title" | "-t" => {
+                        title = Some(args.next().ok_or("missing title")?.to_string())
+                    }
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::NewDay { day, title })
+        }
+        Some("repl") => {
+            let mut day = None;
+            let mut input = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--input" | "-i" => {
+                        let selection = day.map_or(Selection::All, Selection::Day);
+                        input = Some(parse_input(&selection, args.next())?)
+                    }
+                    arg if day.is_none() => match parse_selection(Some(arg))? {
+                        Selection::Day(selected) => day = Some(selected),
+                        _ => return Err("repl only works with a single day".to_string()),
+                    },
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Repl { day, input })
+        }
+        Some("submit") => {
+            let day = match parse_selection(args.next())? {
+                Selection::Day(day) => day,
+                _ => return Err("submit only works with a single day".to_string()),
+            };
+            let mut part = None;
+            let mut answer = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--part" | "-p" => part = Some(parse_part(args.next())?),
+                    "--answer" | "-a" => {
+                        answer = Some(args.next().ok_or("missing answer")?.to_string())
+                    }
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Submit { day, part, answer })
+        }
+        Some("gen") => {
+            let day = match parse_selection(args.next())? {
+                Selection::Day(day) => day,
+                _ => return Err("gen only works with a single day".to_string()),
+            };
+            let mut config = GenConfig::default();
+            let mut output = None;
+            let mut answers = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--size" | "-n" => config.size = parse_count(args.next())?,
+                    "--ratio" | "-r" => match parse_count(args.next())? {
+                        ratio if ratio <= 100 => config.ratio = ratio,
+                        _ => return Err("the ratio is a percentage, 100 at most".to_string()),
+                    },
+                    "--seed" | "-s" => config.seed = Some(parse_count(args.next())? as u64),
+                    "--output" | "-o" => {
+                        output = Some(args.next().ok_or("missing output file")?.to_string())
+                    }
+                    "--answers" | "-a" => {
+                        answers = Some(args.next().ok_or("missing answers file")?.to_string())
+                    }
+                    unexpected => return Err(format!("unexpected argument: {}", unexpected)),
+                }
+            }
+            Ok(Command::Gen {
+                day,
+                config,
+                output,
+                answers,
+            })
+        }
+        Some(unknown) => Err(format!("unknown command: {}", unknown)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_selection(arg: Option<&str>) -> Result<Selection, String> {
+    match arg {
+        Some("all") => Ok(Selection::All),
+        Some(day) => match day.strip_suffix("/all") {
+            Some(year) => puzzle::parse_year(year).map(Selection::Year),
+            None => day.parse().map(Selection::Day),
+        },
+        None => Err("missing day".to_string()),
+    }
+}
+
+fn parse_part(arg: Option<&str>) -> Result<u8, String> {
+    match arg {
+        Some("1") => Ok(1),
+        Some("2") => Ok(2),
+        Some(part) => Err(format!("invalid part: {}", part)),
+        None => Err("missing part".to_string()),
+    }
+}
+
+fn parse_input(selection: &Selection, arg: Option<&str>) -> Result<Source, String> {
+    match (selection, arg) {
+        (Selection::All | Selection::Year(_), _) => {
+            Err("--input only works with a single day".to_string())
+        }
+        (Selection::Day(_), Some(path)) => Ok(Source::from(path)),
+        (Selection::Day(_), None) => Err("missing input file".to_string()),
+    }
+}
+
+fn parse_count(arg: Option<&str>) -> Result<usize, String> {
+    match arg {
+        Some(count) => count
+            .parse()
+            .map_err(|_| format!("invalid count: {}", count)),
+        None => Err("missing count".to_string()),
+    }
+}
+
+#[cfg(test)]
+fn args(input: &str) -> Vec<String> {
+    input.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_parse_run() {
+    assert_eq!(
+        parse_args(&args("run 4 --part 2")),
+        Ok(Command::Run {
+            selection: Selection::Day(PuzzleId::new(2020, 4)),
+            part: Some(2),
+            inputs: vec![],
+            format: Format::Human,
+            trace: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("run all")),
+        Ok(Command::Run {
+            selection: Selection::All,
+            part: None,
+            inputs: vec![],
+            format: Format::Human,
+            trace: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("run 3 --input other.txt -i - --format json")),
+        Ok(Command::Run {
+            selection: Selection::Day(PuzzleId::new(2020, 3)),
+            part: None,
+            inputs: vec![Source::from("other.txt"), Source::Stdin],
+            format: Format::Json,
+            trace: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("run all --trace run.json")),
+        Ok(Command::Run {
+            selection: Selection::All,
+            part: None,
+            inputs: vec![],
+            format: Format::Human,
+            trace: Some("run.json".to_string()),
+        })
+    );
+    assert!(parse_args(&args("run 1 --trace")).is_err());
+    assert!(parse_args(&args("run all --input other.txt")).is_err());
+}
+
+#[test]
+fn test_parse_bench() {
+    assert_eq!(
+        parse_args(&args("bench 1 --samples 50 --output bench.tsv")),
+        Ok(Command::Bench {
+            selection: Selection::Day(PuzzleId::new(2020, 1)),
+            config: BenchConfig {
+                warmup: 3,
+                samples: 50
+            },
+            output: Some("bench.tsv".to_string()),
+            input: None,
+        })
+    );
+    assert!(parse_args(&args("bench all --samples 0")).is_err());
+    assert!(parse_args(&args("bench all --warmup")).is_err());
+}
+
+#[test]
+fn test_parse_accept_and_verify() {
+    assert_eq!(
+        parse_args(&args("accept 5 --part 1")),
+        Ok(Command::Accept {
+            selection: Selection::Day(PuzzleId::new(2020, 5)),
+            part: Some(1)
+        })
+    );
+    assert_eq!(
+        parse_args(&args("verify")),
+        Ok(Command::Verify {
+            selection: Selection::All
+        })
+    );
+    assert_eq!(
+        parse_args(&args("verify 2")),
+        Ok(Command::Verify {
+            selection: Selection::Day(PuzzleId::new(2020, 2))
+        })
+    );
+    assert!(parse_args(&args("accept")).is_err());
+}
+
+#[test]
+fn test_parse_check() {
+    assert_eq!(
+        parse_args(&args("check 1 --input -")),
+        Ok(Command::Check {
+            selection: Selection::Day(PuzzleId::new(2020, 1)),
+            input: Some(Source::Stdin),
+        })
+    );
+    assert!(parse_args(&args("check all -i other.txt")).is_err());
+    assert_eq!(
+        parse_args(&args("alloc all")),
+        Ok(Command::Alloc {
+            selection: Selection::All,
+            input: None,
+        })
+    );
+}
+
+#[test]
+fn test_parse_fetch() {
+    assert_eq!(
+        parse_args(&args("fetch all")),
+        Ok(Command::Fetch {
+            selection: Selection::All
+        })
+    );
+    assert!(parse_args(&args("fetch")).is_err());
+    assert!(parse_args(&args("fetch 2 3")).is_err());
+}
+
+#[test]
+fn test_parse_watch() {
+    assert_eq!(
+        parse_args(&args("watch 4 --interval 200")),
+        Ok(Command::Watch {
+            day: PuzzleId::new(2020, 4),
+            interval: Duration::from_millis(200),
+        })
+    );
+    assert_eq!(
+        parse_args(&args("watch 4")),
+        Ok(Command::Watch {
+            day: PuzzleId::new(2020, 4),
+            interval: Duration::from_millis(500),
+        })
+    );
+    assert!(parse_args(&args("watch all")).is_err());
+}
+
+#[test]
+fn test_parse_summary() {
+    assert_eq!(
+        parse_args(&args("summary")),
+        Ok(Command::Summary { jobs: None })
+    );
+    assert_eq!(
+        parse_args(&args("summary -j 2")),
+        Ok(Command::Summary { jobs: Some(2) })
+    );
+    assert!(parse_args(&args("summary 2")).is_err());
+}
+
+#[test]
+fn test_parse_new_day() {
+    assert_eq!(
+        parse_args(&args("new-day 7")),
+        Ok(Command::NewDay {
+            day: PuzzleId::new(2020, 7),
+            title: None
+        })
+    );
+    let mut with_title = args("new-day 7 --title");
+    with_title.push("Handy Haversacks".to_string());
+    assert_eq!(
+        parse_args(&with_title),
+        Ok(Command::NewDay {
+            day: PuzzleId::new(2020, 7),
+            title: Some("Handy Haversacks".to_string())
+        })
+    );
+    assert!(parse_args(&args("new-day all")).is_err());
+    assert!(parse_args(&args("new-day 26")).is_err());
+}
+
+#[test]
+fn test_parse_gen() {
+    assert_eq!(
+        parse_args(&args("gen 1")),
+        Ok(Command::Gen {
+            day: PuzzleId::new(2020, 1),
+            config: GenConfig::default(),
+            output: None,
+            answers: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args(
+            "gen 3 --size 100000 --ratio 20 --seed 7 -o big.txt --answers big.answers"
+        )),
+        Ok(Command::Gen {
+            day: PuzzleId::new(2020, 3),
+            config: GenConfig {
+                size: 100_000,
+                ratio: 20,
+                seed: Some(7),
+            },
+            output: Some("big.txt".to_string()),
+            answers: Some("big.answers".to_string()),
+        })
+    );
+    assert!(parse_args(&args("gen all")).is_err());
+    assert!(parse_args(&args("gen 2 --ratio 101")).is_err());
+}
+
+#[test]
+fn test_parse_years() {
+    assert_eq!(
+        parse_args(&args("run 2021/3")),
+        Ok(Command::Run {
+            selection: Selection::Day(PuzzleId::new(2021, 3)),
+            part: None,
+            inputs: vec![],
+            format: Format::Human,
+            trace: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("verify 2021/all")),
+        Ok(Command::Verify {
+            selection: Selection::Year(2021)
+        })
+    );
+    assert_eq!(
+        parse_args(&args("new-day 2021/1")),
+        Ok(Command::NewDay {
+            day: PuzzleId::new(2021, 1),
+            title: None
+        })
+    );
+    assert!(parse_args(&args("run 2021/all --input other.txt")).is_err());
+    assert!(parse_args(&args("watch 2021/all")).is_err());
+    assert!(parse_args(&args("run 1999/all")).is_err());
+    assert!(parse_args(&args("new-day 2021/26")).is_err());
+}
+
+#[test]
+fn test_parse_repl() {
+    assert_eq!(
+        parse_args(&args("repl")),
+        Ok(Command::Repl {
+            day: None,
+            input: None
+        })
+    );
+    assert_eq!(
+        parse_args(&args("repl 4 --input batch.txt")),
+        Ok(Command::Repl {
+            day: Some(PuzzleId::new(2020, 4)),
+            input: Some(Source::from("batch.txt")),
+        })
+    );
+    assert!(parse_args(&args("repl --input batch.txt")).is_err());
+    assert!(parse_args(&args("repl all")).is_err());
+    assert!(parse_args(&args("repl 4 5")).is_err());
+}
+
+#[test]
+fn test_parse_submit() {
+    assert_eq!(
+        parse_args(&args("submit 5")),
+        Ok(Command::Submit {
+            day: PuzzleId::new(2020, 5),
+            part: None,
+            answer: None,
+        })
+    );
+    assert_eq!(
+        parse_args(&args("submit 2021/1 --part 2 --answer 1523")),
+        Ok(Command::Submit {
+            day: PuzzleId::new(2021, 1),
+            part: Some(2),
+            answer: Some("1523".to_string()),
+        })
+    );
+    assert!(parse_args(&args("submit all")).is_err());
+    assert!(parse_args(&args("submit 5 --part 3")).is_err());
+    assert!(parse_args(&args("submit 5 --answer")).is_err());
+}
+
+#[test]
+fn test_parse_list() {
+    assert_eq!(parse_args(&args("list")), Ok(Command::List));
+}
+
+#[test]
+fn test_parse_invalid() {
+    assert!(parse_args(&args("")).is_err());
+    assert!(parse_args(&args("run")).is_err());
+    assert!(parse_args(&args("run four")).is_err());
+    assert!(parse_args(&args("run 4 --part 3")).is_err());
+    assert!(parse_args(&args("fly 4")).is_err());
+}
@@ -0,0 +1,154 @@
+use common::bench::{BenchConfig, BenchResult};
+use common::error::ParseError;
+use common::input::{self, Input, InputError, Source};
+use common::memory::AllocProfile;
+use common::puzzle::PuzzleId;
+use common::{Found, Solved};
+use std::path::{Path, PathBuf};
+
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    pub title: &'static str,
+    pub solve: fn(&str, &[u8]) -> Result<Vec<Solved>, ParseError>,
+    pub bench: fn(&str, &BenchConfig) -> Result<Vec<BenchResult>, ParseError>,
+    pub profile: fn(&str) -> Result<Vec<AllocProfile>, ParseError>,
+    /// Alternative implementations of a part, benchmarked next to the main solution and checked
+    /// against it by `aoc check`.
+    pub variants: &'static [Variant],
+}
+
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str) -> Result<String, ParseError>,
+}
+
+impl Day {
+    pub fn id(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.number)
+    }
+
+    /// The directory of the day's crate, in the same workspace as this one.
+    pub fn crate_dir(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("the runner lives inside the workspace")
+            .join(self.id().crate_path())
+    }
+
+    pub fn read_input(&self, source: Option<&Source>) -> Result<Input, InputError> {
+        input::read(self.id(), &self.crate_dir(), source)
+    }
+}
+
+pub const DAYS: [Day; 6] = [
+    Day {
+        year: 2020,
+        number: 1,
+        title: "Report Repair",
+        solve: common::solve::<day1::ExpenseReport>,
+        bench: common::bench::bench_solution::<day1::ExpenseReport>,
+        profile: common::memory::profile_solution::<day1::ExpenseReport>,
+        variants: &[
+            Variant {
+                name: "day1_borrow",
+                part: 1,
+                solve: |input| {
+                    day1::day1_borrow(&input.lines().collect::<Vec<_>>(), 2)
+                        .map(|answer| Found(answer).to_string())
+                },
+            },
+            Variant {
+                name: "day1_no_borrow",
+                part: 1,
+                solve: |input| {
+                    day1::day1_no_borrow(input.lines().collect(), 2)
+                        .map(|answer| Found(answer).to_string())
+                },
+            },
+            Variant {
+                name: "day1_borrow",
+                part: 2,
+                solve: |input| {
+                    day1::day1_borrow(&input.lines().collect::<Vec<_>>(), 3)
+                        .map(|answer| Found(answer).to_string())
+                },
+            },
+            Variant {
+                name: "day1_no_borrow",
+                part: 2,
+                solve: |input| {
+                    day1::day1_no_borrow(input.lines().collect(), 3)
+                        .map(|answer| Found(answer).to_string())
+                },
+            },
+        ],
+    },
+    Day {
+        year: 2020,
+        number: 2,
+        title: "Password Philosophy",
+        solve: common::solve::<day2::RecoveredPassword>,
+        bench: common::bench::bench_solution::<day2::RecoveredPassword>,
+        profile: common::memory::profile_solution::<day2::RecoveredPassword>,
+        variants: &[],
+    },
+    Day {
+        year: 2020,
+        number: 3,
+        title: "Toboggan Trajectory",
+        solve: common::solve::<day3::Toboggan>,
+        bench: common::bench::bench_solution::<day3::Toboggan>,
+        profile: common::memory::profile_solution::<day3::Toboggan>,
+        variants: &[],
+    },
+    Day {
+        year: 2020,
+        number: 4,
+        title: "Passport Processing",
+        solve: common::solve::<day4::CredentialsValidator>,
+        bench: common::bench::bench_solution::<day4::CredentialsValidator>,
+        profile: common::memory::profile_solution::<day4::CredentialsValidator>,
+        variants: &[],
+    },
+    Day {
+        year: 2020,
+        number: 5,
+        title: "Binary Boarding",
+        solve: common::solve::<day5::SeatCode>,
+        bench: common::bench::bench_solution::<day5::SeatCode>,
+        profile: common::memory::profile_solution::<day5::SeatCode>,
+        variants: &[
+            Variant {
+                name: "decode_seat_id",
+                part: 1,
+                solve: |input| {
+                    let max = input.lines().map(day5::decode_seat_id).max();
+                    Ok(Found(max).to_string())
+                },
+            },
+            Variant {
+                name: "decode_seat_id",
+                part: 2,
+                solve: |input| {
+                    let seat_ids: Vec<i32> = input.lines().map(day5::decode_seat_id).collect();
+                    Ok(Found(day5::find_free_seat(&seat_ids)).to_string())
+                },
+            },
+        ],
+    },
+    Day {
+        year: 2020,
+        number: 6,
+        title: "Custom Customs",
+        solve: common::solve::<day6::GroupAnswers>,
+        bench: common::bench::bench_solution::<day6::GroupAnswers>,
+        profile: common::memory::profile_solution::<day6::GroupAnswers>,
+        variants: &[],
+    },
+];
+
+pub fn find(puzzle: PuzzleId) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.id() == puzzle)
+}
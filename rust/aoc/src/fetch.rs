@@ -0,0 +1,461 @@
+use common::input::INPUT_FILE;
+use common::puzzle::PuzzleId;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::{read_to_string, rename, write};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session cookie of the website. It takes precedence over
+/// the config file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable overriding the website inputs are downloaded from and answers are
+/// submitted to.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable pointing at the config file, instead of `~/.config/aoc/config`.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+/// Environment variable overriding the user agent requests are made with, so the maintainers of
+/// the website can tell who to contact. It takes precedence over the config file.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+pub const DEFAULT_USER_AGENT: &str = "github.com/douglascamata/aoc_2020";
+
+/// Where to download inputs from, and submit answers to, and as whom.
+#[derive(Debug, PartialEq)]
+pub struct FetchConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub user_agent: String,
+    /// The config file that was looked for, to point at when the session is missing.
+    pub config_file: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// There is no session token, neither in the environment nor in the config file.
+    MissingToken {
+        config_file: Option<PathBuf>,
+    },
+    /// The config file exists but is malformed.
+    Config {
+        path: PathBuf,
+        message: String,
+    },
+    /// The website answered 404: the puzzle isn't out yet (or doesn't exist).
+    NotUnlocked {
+        puzzle: PuzzleId,
+    },
+    /// Any other answer than 200, with the first line of its body.
+    Status {
+        url: String,
+        code: u16,
+        message: String,
+    },
+    /// The website couldn't be reached at all.
+    Http {
+        url: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingToken { config_file } => {
+                write!(f, "no session token, set ${}", SESSION_ENV)?;
+                match config_file {
+                    Some(path) => write!(f, " or add `session: TOKEN` to {}", path.display()),
+                    None => Ok(()),
+                }
+            }
+            FetchError::Config { path, message } => write!(f, "{}: {}", path.display(), message),
+            FetchError::NotUnlocked { puzzle } => {
+                write!(f, "the puzzle of {} isn't unlocked yet", puzzle)
+            }
+            FetchError::Status { url, code, message } => {
+                write!(f, "{} answered {}: {}", url, code, message)
+            }
+            FetchError::Http { url, message } => write!(f, "couldn't reach {}: {}", url, message),
+            FetchError::Io { path, source } => {
+                write!(f, "couldn't write {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// What [`fetch`] did.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already there, nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl FetchConfig {
+    /// Reads the config file, when there is one, and then the environment, which wins.
+    pub fn load() -> Result<Self, FetchError> {
+        let config_file = env::var_os(CONFIG_ENV).map(PathBuf::from).or_else(|| {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+                .map(|dir| dir.join("aoc").join("config"))
+        });
+        let mut config = match &config_file {
+            Some(path) => match read_to_string(path) {
+                Ok(contents) => Self::parse(&contents).map_err(|message| FetchError::Config {
+                    path: path.clone(),
+                    message,
+                })?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+                Err(err) => {
+                    return Err(FetchError::Config {
+                        path: path.clone(),
+                        message: err.to_string(),
+                    })
+                }
+            },
+            None => Self::default(),
+        };
+        config.config_file = config_file;
+        if let Some(session) = env::var(SESSION_ENV).ok().filter(|s| !s.is_empty()) {
+            config.session = Some(session);
+        }
+        if let Some(base_url) = env::var(BASE_URL_ENV).ok().filter(|s| !s.is_empty()) {
+            config.base_url = base_url;
+        }
+        if let Some(user_agent) = env::var(USER_AGENT_ENV).ok().filter(|s| !s.is_empty()) {
+            config.user_agent = user_agent;
+        }
+        Ok(config)
+    }
+
+    /// Parses a config file of `key: value` lines, with `session`, `base_url` and `user_agent` as
+    /// keys.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(':') {
+                Some((key, value)) => match key.trim() {
+                    "session" => config.session = Some(value.trim().to_string()),
+                    "base_url" => config.base_url = value.trim().to_string(),
+                    "user_agent" => config.user_agent = value.trim().to_string(),
+                    key => return Err(format!("line {}: unknown key {:?}", number + 1, key)),
+                },
+                None => return Err(format!("line {}: expected `key: value`", number + 1)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// The address of the input of `puzzle`.
+    pub fn input_url(&self, puzzle: PuzzleId) -> String {
+        format!("{}/input", self.puzzle_url(puzzle))
+    }
+
+    /// The address answers to `puzzle` are posted to.
+    pub fn answer_url(&self, puzzle: PuzzleId) -> String {
+        format!("{}/answer", self.puzzle_url(puzzle))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url.trim_end_matches('/'),
+            puzzle.year,
+            puzzle.day
+        )
+    }
+
+    /// The session token, which every request to the website needs.
+    pub fn session(&self) -> Result<&str, FetchError> {
+        self.session
+            .as_deref()
+            .ok_or_else(|| FetchError::MissingToken {
+                config_file: self.config_file.clone(),
+            })
+    }
+
+    /// An HTTP client that identifies itself to the website, as its maintainers ask for.
+    pub fn agent(&self) -> ureq::Agent {
+        ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&self.user_agent)
+            .build()
+    }
+}
+
+/// The error for a request to `url` that failed, with the first line of the body of any answer.
+pub fn request_error(url: String, err: ureq::Error) -> FetchError {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            FetchError::Status {
+                url,
+                code,
+                message: body.lines().next().unwrap_or("").trim().to_string(),
+            }
+        }
+        ureq::Error::Transport(transport) => FetchError::Http {
+            url,
+            message: transport.to_string(),
+        },
+    }
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            config_file: None,
+        }
+    }
+}
+
+/// Downloads the input of `puzzle` into the `input.txt` of its crate, unless it's already there.
+/// A cached input is never downloaded again.
+pub fn fetch(
+    config: &FetchConfig,
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+) -> Result<Fetched, FetchError> {
+    let path = crate_dir.join(INPUT_FILE);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+    let session = config.session()?;
+
+    let url = config.input_url(puzzle);
+    let response = config
+        .agent()
+        .get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .call();
+    let contents = match response {
+        Ok(response) => response.into_string().map_err(|err| FetchError::Http {
+            url: url.clone(),
+            message: err.to_string(),
+        })?,
+        Err(ureq::Error::Status(404, _)) => return Err(FetchError::NotUnlocked { puzzle }),
+        Err(err) => return Err(request_error(url, err)),
+    };
+
+    // Write next to the input and move it in place, so an interrupted download is never
+    // mistaken for a cached input.
+    let partial = crate_dir.join(format!("{}.part", INPUT_FILE));
+    write(&partial, contents).map_err(|source| FetchError::Io {
+        path: partial.clone(),
+        source,
+    })?;
+    rename(&partial, &path).map_err(|source| FetchError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> common::scratch::ScratchDir {
+    common::scratch::ScratchDir::new(&format!("fetch-{}", name))
+}
+
+/// Serves a single request with `status` and `body` on a local port. Returns the base URL to
+/// use and a handle giving back the request that was received, its body after a blank line.
+#[cfg(test)]
+pub fn stub_server(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            request.push_str(&line);
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        request.push_str("\r\n");
+        request.push_str(&String::from_utf8(body).unwrap());
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+        request
+    });
+    (base_url, handle)
+}
+
+#[cfg(test)]
+pub fn config(base_url: &str, session: Option<&str>) -> FetchConfig {
+    FetchConfig {
+        base_url: base_url.to_string(),
+        session: session.map(String::from),
+        user_agent: "aoc tests".to_string(),
+        config_file: Some(PathBuf::from("/home/elf/.config/aoc/config")),
+    }
+}
+
+#[test]
+fn test_fetch_downloads_and_caches() {
+    let crate_dir = scratch_dir("download");
+    let (base_url, server) = stub_server("200 OK", "1721\n979\n");
+
+    let fetched = fetch(
+        &config(&base_url, Some("cookie")),
+        PuzzleId::new(2020, 3),
+        &crate_dir,
+    )
+    .unwrap();
+    assert_eq!(fetched, Fetched::Downloaded(crate_dir.join("input.txt")));
+    assert_eq!(
+        read_to_string(crate_dir.join("input.txt")).unwrap(),
+        "1721\n979\n"
+    );
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /2020/day/3/input HTTP/1.1\r\n"));
+    assert!(request.contains("session=cookie"));
+    assert!(request.contains("\r\nUser-Agent: aoc tests\r\n"));
+
+    // Nothing listens there anymore, so any download attempt would fail.
+    let fetched = fetch(
+        &config(&base_url, Some("cookie")),
+        PuzzleId::new(2020, 3),
+        &crate_dir,
+    )
+    .unwrap();
+    assert_eq!(fetched, Fetched::Cached(crate_dir.join("input.txt")));
+}
+
+#[test]
+fn test_fetch_not_unlocked() {
+    let crate_dir = scratch_dir("locked");
+    let (base_url, server) = stub_server(
+        "404 Not Found",
+        "Please don't repeatedly request this endpoint before it unlocks!",
+    );
+
+    match fetch(
+        &config(&base_url, Some("cookie")),
+        PuzzleId::new(2021, 25),
+        &crate_dir,
+    ) {
+        Err(FetchError::NotUnlocked { puzzle }) => assert_eq!(puzzle, PuzzleId::new(2021, 25)),
+        other => panic!("expected NotUnlocked, got {:?}", other),
+    }
+    server.join().unwrap();
+    assert!(!crate_dir.join("input.txt").exists());
+}
+
+#[test]
+fn test_fetch_reports_other_statuses() {
+    let crate_dir = scratch_dir("status");
+    let (base_url, server) = stub_server(
+        "400 Bad Request",
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+    );
+
+    let err = fetch(
+        &config(&base_url, Some("expired")),
+        PuzzleId::new(2020, 1),
+        &crate_dir,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "{}/2020/day/1/input answered 400: Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            base_url
+        )
+    );
+    server.join().unwrap();
+    assert!(!crate_dir.join("input.txt").exists());
+}
+
+#[test]
+fn test_fetch_needs_a_token() {
+    let crate_dir = scratch_dir("token");
+    let err = fetch(
+        &config("http://127.0.0.1:9", None),
+        PuzzleId::new(2020, 1),
+        &crate_dir,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no session token, set $AOC_SESSION or add `session: TOKEN` to /home/elf/.config/aoc/config"
+    );
+}
+
+#[test]
+fn test_parse_config() {
+    let config =
+        FetchConfig::parse("# mine\nsession: abc123\nbase_url: http://localhost:8080/\n").unwrap();
+    assert_eq!(config.session.as_deref(), Some("abc123"));
+    assert_eq!(config.user_agent, DEFAULT_USER_AGENT);
+    assert_eq!(
+        FetchConfig::parse("user_agent: me at example.org").map(|config| config.user_agent),
+        Ok("me at example.org".to_string())
+    );
+    assert_eq!(
+        config.input_url(PuzzleId::new(2020, 4)),
+        "http://localhost:8080/2020/day/4/input"
+    );
+    assert_eq!(
+        FetchConfig::parse("")
+            .unwrap()
+            .input_url(PuzzleId::new(2020, 1)),
+        "https://adventofcode.com/2020/day/1/input"
+    );
+    assert_eq!(
+        FetchConfig::default().input_url(PuzzleId::new(2021, 12)),
+        "https://adventofcode.com/2021/day/12/input"
+    );
+    assert_eq!(
+        config.answer_url(PuzzleId::new(2020, 4)),
+        "http://localhost:8080/2020/day/4/answer"
+    );
+    assert!(FetchConfig::parse("token abc").is_err());
+    assert!(FetchConfig::parse("cookie: abc").is_err());
+}
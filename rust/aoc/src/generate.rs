@@ -0,0 +1,432 @@
+use common::answers::KnownAnswers;
+use common::puzzle::{PuzzleId, DEFAULT_YEAR};
+use common::random::Rng;
+use common::Found;
+use std::collections::BTreeSet;
+
+/// How big a generated input is, and how it is made.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenConfig {
+    /// The number of entries, lines, rows, passports, boarding passes or groups.
+    pub size: usize,
+    /// The share, in percent, of valid passwords, trees, valid passports or "yes" answers.
+    pub ratio: usize,
+    /// `None` picks a seed from the clock.
+    pub seed: Option<u64>,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        GenConfig {
+            size: 1000,
+            ratio: 50,
+            seed: None,
+        }
+    }
+}
+
+/// A generated input with the answers it was made to give.
+#[derive(Debug, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: KnownAnswers,
+    /// Anything the user should know about the input, like a part left without an answer.
+    pub notes: Vec<String>,
+}
+
+type Generator = fn(&mut Rng, &GenConfig) -> Result<Generated, String>;
+
+/// The generator of a puzzle, if it has one.
+pub fn generator(puzzle: PuzzleId) -> Option<Generator> {
+    match (puzzle.year, puzzle.day) {
+        (DEFAULT_YEAR, 1) => Some(expense_report),
+        (DEFAULT_YEAR, 2) => Some(password_database),
+        (DEFAULT_YEAR, 3) => Some(tree_map),
+        (DEFAULT_YEAR, 4) => Some(passport_batch),
+        (DEFAULT_YEAR, 5) => Some(boarding_passes),
+        (DEFAULT_YEAR, 6) => Some(customs_answers),
+        _ => None,
+    }
+}
+
+fn answers(part1: impl ToString, part2: Option<impl ToString>) -> KnownAnswers {
+    let mut answers = KnownAnswers::default();
+    answers.set(1, part1.to_string());
+    if let Some(part2) = part2 {
+        answers.set(2, part2.to_string());
+    }
+    answers
+}
+
+fn lines(lines: Vec<String>) -> String {
+    let mut input = lines.join("\n");
+    input.push('\n');
+    input
+}
+
+/// Day 1: `size` entries where exactly one pair and one triple sum to 2020.
+///
+/// The planted pair is a small entry and a big one, the triple three small entries. Every other
+/// entry is big (above 1010), so two of them already sum to more than 2020, and none of them is
+/// allowed to complete a pair or a triple with the small entries.
+fn expense_report(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    if config.size < 5 {
+        return Err("day 1 needs at least 5 entries".to_string());
+    }
+    let (pair, triple) = loop {
+        let (x, y) = (rng.range(1, 1009), rng.range(1, 1009));
+        let z = 2020 - x - y;
+        let a = rng.range(1, 1009);
+        let planted = [a, 2020 - a, x, y, z];
+        if (1..=1009).contains(&z) && sums_to_2020(&planted) == (1, 1) {
+            break ([a, 2020 - a], [x, y, z]);
+        }
+    };
+    let small = [pair[0], triple[0], triple[1], triple[2]];
+    let mut forbidden: BTreeSet<i64> = small.iter().map(|s| 2020 - s).collect();
+    for (i, first) in small.iter().enumerate() {
+        for second in &small[i + 1..] {
+            forbidden.insert(2020 - first - second);
+        }
+    }
+
+    let mut entries: Vec<i64> = pair.iter().chain(&triple).copied().collect();
+    while entries.len() < config.size {
+        let entry = rng.range(1011, 2020);
+        if !forbidden.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+    rng.shuffle(&mut entries);
+    Ok(Generated {
+        input: lines(entries.iter().map(i64::to_string).collect()),
+        answers: answers(pair[0] * pair[1], Some(triple.iter().product::<i64>())),
+        notes: vec![],
+    })
+}
+
+/// How many pairs and triples of `entries` sum to 2020.
+fn sums_to_2020(entries: &[i64]) -> (usize, usize) {
+    let n = entries.len();
+    let mut pairs = 0;
+    let mut triples = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            pairs += usize::from(entries[i] + entries[j] == 2020);
+            for k in j + 1..n {
+                triples += usize::from(entries[i] + entries[j] + entries[k] == 2020);
+            }
+        }
+    }
+    (pairs, triples)
+}
+
+/// Day 2: `size` database lines, each valid for each part with a chance of `ratio` percent.
+fn password_database(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    let letters = ['a', 'b', 'c', 'd', 'e'];
+    let mut database = Vec::with_capacity(config.size);
+    let (mut valid1, mut valid2) = (0, 0);
+    for _ in 0..config.size {
+        let wanted = (rng.chance(config.ratio, 100), rng.chance(config.ratio, 100));
+        // Random lines come out in every combination often enough to just draw until one
+        // matches.
+        let line = loop {
+            let min = rng.range(1, 10) as usize;
+            let max = rng.range(min as i64 + 1, 20) as usize;
+            let c = *rng.pick(&letters);
+            let density = rng.below(101);
+            let password: Vec<char> = (0..rng.range(1, 24))
+                .map(|_| {
+                    if rng.chance(density, 100) {
+                        c
+                    } else {
+                        *rng.pick(&letters)
+                    }
+                })
+                .collect();
+            let count = password.iter().filter(|&&p| p == c).count();
+            let at = |position: usize| password.get(position - 1) == Some(&c);
+            if ((min..=max).contains(&count), at(min) != at(max)) == wanted {
+                let password: String = password.into_iter().collect();
+                break format!("{}-{} {}: {}", min, max, c, password);
+            }
+        };
+        valid1 += usize::from(wanted.0);
+        valid2 += usize::from(wanted.1);
+        database.push(line);
+    }
+    Ok(Generated {
+        input: lines(database),
+        answers: answers(valid1, Some(valid2)),
+        notes: vec![],
+    })
+}
+
+/// The slopes of day 3, as `(right, down)`, part 1 using the second one.
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// Day 3: a map `size` rows high and 31 columns wide, like the real ones, with a tree on
+/// `ratio` percent of the squares.
+fn tree_map(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    const WIDTH: usize = 31;
+    if config.size == 0 {
+        return Err("day 3 needs at least 1 row".to_string());
+    }
+    let map: Vec<Vec<bool>> = (0..config.size)
+        .map(|_| (0..WIDTH).map(|_| rng.chance(config.ratio, 100)).collect())
+        .collect();
+    let trees: Vec<u128> = SLOPES
+        .iter()
+        .map(|&(right, down)| {
+            (1..)
+                .map(|step| (step * right % WIDTH, step * down))
+                .take_while(|&(_, y)| y < map.len())
+                .filter(|&(x, y)| map[y][x])
+                .count() as u128
+        })
+        .collect();
+    // Like the solution, slopes that hit no tree are left out of the product, and a product
+    // that doesn't fit in 128 bits is no answer.
+    let product = trees
+        .iter()
+        .filter(|&&count| count != 0)
+        .try_fold(1u128, |product, &count| product.checked_mul(count));
+    let rows = map
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&tree| if tree { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    Ok(Generated {
+        input: lines(rows),
+        answers: answers(trees[1], Some(Found(product))),
+        notes: vec![],
+    })
+}
+
+/// Day 4: `size` passports, `ratio` percent of them valid. The others either miss a field,
+/// which makes them invalid for both parts, or have one invalid value, which only matters to
+/// part 2.
+fn passport_batch(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    let mut passports = Vec::with_capacity(config.size);
+    let (mut complete, mut valid) = (0, 0);
+    for _ in 0..config.size {
+        let mut fields: Vec<(&str, String)> = vec![
+            ("byr", rng.range(1920, 2002).to_string()),
+            ("iyr", rng.range(2010, 2020).to_string()),
+            ("eyr", rng.range(2020, 2030).to_string()),
+            (
+                "hgt",
+                if rng.chance(1, 2) {
+                    format!("{}cm", rng.range(150, 193))
+                } else {
+                    format!("{}in", rng.range(59, 76))
+                },
+            ),
+            ("hcl", format!("#{:06x}", rng.below(0x100_0000))),
+            (
+                "ecl",
+                rng.pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+                    .to_string(),
+            ),
+            ("pid", format!("{:09}", rng.below(1_000_000_000))),
+        ];
+        if rng.chance(config.ratio, 100) {
+            complete += 1;
+            valid += 1;
+        } else if rng.chance(1, 2) {
+            fields.remove(rng.below(fields.len()));
+        } else {
+            complete += 1;
+            let index = rng.below(fields.len());
+            fields[index].1 = match fields[index].0 {
+                "byr" => rng.range(1850, 1919).to_string(),
+                "iyr" => rng.range(2021, 2040).to_string(),
+                "eyr" => rng.range(1990, 2019).to_string(),
+                "hgt" => format!("{}", rng.range(150, 193)),
+                "hcl" => format!("{:06x}", rng.below(0x100_0000)),
+                "ecl" => rng.pick(&["xyz", "zzz", "gmt"]).to_string(),
+                _ => format!("{:08}", rng.below(100_000_000)),
+            };
+        }
+        if rng.chance(1, 2) {
+            fields.push(("cid", rng.range(1, 350).to_string()));
+        }
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (index, (key, value)) in fields.iter().enumerate() {
+            if index > 0 {
+                passport.push(if rng.chance(1, 3) { '\n' } else { ' ' });
+            }
+            passport.push_str(&format!("{}:{}", key, value));
+        }
+        passports.push(passport);
+    }
+    Ok(Generated {
+        input: passports.join("\n\n") + "\n",
+        answers: answers(complete, Some(valid)),
+        notes: vec![],
+    })
+}
+
+/// The boarding pass of a seat ID: the ID in binary, with `B` and `R` as ones.
+fn boarding_pass(id: usize) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| match (bit >= 3, id >> bit & 1 == 1) {
+            (true, false) => 'F',
+            (true, true) => 'B',
+            (false, false) => 'L',
+            (false, true) => 'R',
+        })
+        .collect()
+}
+
+/// Day 5: `size` boarding passes for a block of consecutive seats with one free seat inside.
+/// The plane only has 1024 seats, which caps the size.
+fn boarding_passes(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    if !(2..=1023).contains(&config.size) {
+        return Err("day 5 needs 2 to 1023 boarding passes, the plane has 1024 seats".to_string());
+    }
+    let first = rng.below(1024 - config.size);
+    let last = first + config.size;
+    let free = first + 1 + rng.below(config.size - 1);
+    let mut passes: Vec<String> = (first..=last)
+        .filter(|&id| id != free)
+        .map(boarding_pass)
+        .collect();
+    rng.shuffle(&mut passes);
+    Ok(Generated {
+        input: lines(passes),
+        answers: answers(last, Some(free)),
+        notes: vec![],
+    })
+}
+
+/// Day 6: `size` groups of 1 to 5 people, each answering "yes" to every question with a chance
+/// of `ratio` percent, and to at least one question.
+fn customs_answers(rng: &mut Rng, config: &GenConfig) -> Result<Generated, String> {
+    let mut groups = Vec::with_capacity(config.size);
+    let (mut anyone, mut everyone) = (0, 0);
+    for _ in 0..config.size {
+        let people: Vec<BTreeSet<char>> = (0..rng.range(1, 5))
+            .map(|_| loop {
+                let person: BTreeSet<char> = ('a'..='z')
+                    .filter(|_| rng.chance(config.ratio, 100))
+                    .collect();
+                if !person.is_empty() {
+                    break person;
+                }
+                if config.ratio == 0 {
+                    break std::iter::once(*rng.pick(&['a', 'b', 'c'])).collect();
+                }
+            })
+            .collect();
+        let union: BTreeSet<char> = people.iter().flatten().copied().collect();
+        anyone += union.len();
+        everyone += union
+            .iter()
+            .filter(|question| people.iter().all(|person| person.contains(question)))
+            .count();
+        let lines: Vec<String> = people
+            .iter()
+            .map(|person| {
+                let mut questions: Vec<char> = person.iter().copied().collect();
+                rng.shuffle(&mut questions);
+                questions.into_iter().collect()
+            })
+            .collect();
+        groups.push(lines.join("\n"));
+    }
+    Ok(Generated {
+        input: groups.join("\n\n") + "\n",
+        answers: answers(anyone, Some(everyone)),
+        notes: vec![],
+    })
+}
+
+#[cfg(test)]
+fn solve_generated(day: PuzzleId, config: &GenConfig) -> (Generated, Vec<String>) {
+    let generated = generator(day).unwrap()(&mut Rng::new(config.seed.unwrap()), config).unwrap();
+    let solved = (crate::days::find(day).unwrap().solve)(&generated.input, &[1, 2]).unwrap();
+    (generated, solved.into_iter().map(|s| s.answer).collect())
+}
+
+#[test]
+fn test_generated_answers_match_the_solutions() {
+    for day in 1..=6 {
+        for &(size, ratio) in &[(5, 0), (40, 30), (200, 50), (300, 100)] {
+            let config = GenConfig {
+                size,
+                ratio,
+                seed: Some(day as u64 * 1000 + size as u64),
+            };
+            let (generated, solved) = solve_generated(PuzzleId::new(2020, day), &config);
+            for (part, answer) in (1..=2).zip(&solved) {
+                if let Some(expected) = generated.answers.get(part) {
+                    assert_eq!(
+                        answer, expected,
+                        "day {} part {} with {:?}",
+                        day, part, config
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_big_tree_maps_have_a_part2() {
+    let config = GenConfig {
+        size: 200_000,
+        ratio: 60,
+        seed: Some(1),
+    };
+    let (generated, solved) = solve_generated(PuzzleId::new(2020, 3), &config);
+    assert!(solved[1].parse::<u128>().unwrap() > u64::MAX as u128);
+    assert_eq!(generated.answers.get(2), Some(solved[1].as_str()));
+}
+
+#[test]
+fn test_generators_are_seedable() {
+    let config = GenConfig {
+        size: 50,
+        ratio: 50,
+        seed: Some(7),
+    };
+    for day in 1..=6 {
+        let generate = generator(PuzzleId::new(2020, day)).unwrap();
+        let once = generate(&mut Rng::new(7), &config).unwrap();
+        assert_eq!(once, generate(&mut Rng::new(7), &config).unwrap());
+        assert_ne!(once, generate(&mut Rng::new(8), &config).unwrap());
+    }
+}
+
+#[test]
+fn test_planted_answers() {
+    let config = GenConfig {
+        size: 30,
+        ratio: 50,
+        seed: Some(1),
+    };
+    let report = expense_report(&mut Rng::new(1), &config).unwrap();
+    let entries: Vec<i64> = report.input.lines().map(|l| l.parse().unwrap()).collect();
+    assert_eq!(entries.len(), 30);
+    assert_eq!(sums_to_2020(&entries), (1, 1));
+
+    let passes = boarding_passes(&mut Rng::new(1), &config).unwrap();
+    assert_eq!(passes.input.lines().count(), 30);
+    assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+    assert!(boarding_passes(
+        &mut Rng::new(1),
+        &GenConfig {
+            size: 1024,
+            ..config
+        }
+    )
+    .is_err());
+    assert!(generator(PuzzleId::new(2020, 7)).is_none());
+    assert!(generator(PuzzleId::new(2021, 1)).is_none());
+}
@@ -0,0 +1,453 @@
+mod check;
+mod cli;
+mod days;
+mod fetch;
+mod generate;
+mod repl;
+mod scaffold;
+mod submit;
+mod summary;
+mod watch;
+
+use cli::{Command, Selection};
+use common::answers::{KnownAnswers, Verdict};
+use common::bench::{self, BenchConfig, BenchResult};
+use common::error::ParseError;
+use common::input::{Input, Source};
+use common::memory::{self, AllocProfile};
+use common::output::{Format, Record};
+use common::puzzle::PuzzleId;
+use common::random::Rng;
+use common::trace;
+use days::{Day, DAYS};
+use fetch::{FetchConfig, Fetched};
+use generate::GenConfig;
+use std::fs::write;
+use std::path::Path;
+use std::process;
+use submit::Outcome;
+
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = cli::parse_args(&args).unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, cli::USAGE);
+        process::exit(2);
+    });
+
+    match command {
+        Command::List => list(),
+        Command::Run {
+            selection,
+            part,
+            inputs,
+            format,
+            trace: trace_file,
+        } => {
+            if let Some(header) = format.header() {
+                println!("{}", header);
+            }
+            if trace_file.is_some() {
+                trace::start();
+            }
+            select(selection)
+                .into_iter()
+                .for_each(|day| run(day, part, &inputs, format));
+            if let (Some(path), Some(trace)) = (trace_file, trace::finish()) {
+                if !common::cli::write_trace(Path::new(&path), &trace) {
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Bench {
+            selection,
+            config,
+            output,
+            input,
+        } => {
+            let results: Vec<BenchResult> = select(selection)
+                .into_iter()
+                .flat_map(|day| run_bench(day, &config, input.as_ref()))
+                .collect();
+            print!("{}", bench::table(&results));
+            if let Some(path) = output {
+                write(&path, bench::tsv(&results)).expect("couldn't write benchmark results");
+                eprintln!("results written to {}", path);
+            }
+        }
+        Command::Accept { selection, part } => {
+            select(selection)
+                .into_iter()
+                .for_each(|day| accept(day, part));
+        }
+        Command::Verify { selection } => {
+            let mut all_match = true;
+            for day in select(selection) {
+                all_match &= verify(day);
+            }
+            if !all_match {
+                process::exit(1);
+            }
+        }
+        Command::Alloc { selection, input } => {
+            if !memory::is_installed() {
+                eprintln!(
+                    "error: allocations aren't counted, build with `--features alloc-profile`"
+                );
+                process::exit(2);
+            }
+            let profiles: Vec<AllocProfile> = select(selection)
+                .into_iter()
+                .flat_map(|day| run_profile(day, input.as_ref()))
+                .collect();
+            print!("{}", memory::table(&profiles));
+        }
+        Command::Check { selection, input } => {
+            let mut all_agree = true;
+            for day in select(selection) {
+                all_agree &= check(day, input.as_ref());
+            }
+            if !all_agree {
+                process::exit(1);
+            }
+        }
+        Command::NewDay { day, title } => {
+            let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("the runner lives inside the workspace");
+            match scaffold::new_day(workspace, day, title.as_deref()) {
+                Ok(written) => {
+                    written
+                        .iter()
+                        .for_each(|path| println!("wrote {}", path.display()));
+                    println!(
+                        "{} is ready, its input goes in {}",
+                        day,
+                        day.crate_path().join("input.txt").display()
+                    );
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Gen {
+            day,
+            config,
+            output,
+            answers,
+        } => generate(day, &config, output.as_deref(), answers.as_deref()),
+        Command::Repl { day, input } => repl::repl(day, input),
+        Command::Summary { jobs } => {
+            let jobs = jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(4, std::num::NonZeroUsize::get)
+            });
+            let days: Vec<&Day> = DAYS.iter().collect();
+            let rows = summary::summarize(&days, jobs);
+            print!("{}", summary::table(&rows));
+            if rows.iter().any(|row| row.status.is_failure()) {
+                process::exit(1);
+            }
+        }
+        Command::Watch { day, interval } => {
+            let day = select(Selection::Day(day))[0];
+            watch::watch(day, interval)
+        }
+        Command::Submit { day, part, answer } => {
+            let day = select(Selection::Day(day))[0];
+            if !submit(day, part, answer) {
+                process::exit(1);
+            }
+        }
+        Command::Fetch { selection } => {
+            let config = load_config();
+            let mut all_fetched = true;
+            for day in select(selection) {
+                all_fetched &= fetch(day, &config);
+            }
+            if !all_fetched {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn select(selection: Selection) -> Vec<&'static Day> {
+    match selection {
+        Selection::All => DAYS.iter().collect(),
+        Selection::Year(year) => {
+            let days: Vec<&Day> = DAYS.iter().filter(|day| day.year == year).collect();
+            if days.is_empty() {
+                eprintln!("error: no day of {} is solved yet", year);
+                process::exit(1);
+            }
+            days
+        }
+        Selection::Day(puzzle) => match days::find(puzzle) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("error: {} is not solved yet", puzzle);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+fn list() {
+    DAYS.iter()
+        .for_each(|day| println!("{}: {}", day.id(), day.title));
+}
+
+fn read_input(day: &Day, source: Option<&Source>) -> Input {
+    day.read_input(source).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Exits with an error when `input` couldn't be parsed.
+fn parsed<T>(day: &Day, input: &Input, result: Result<T, ParseError>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("error: {}: {}: {}", day.id(), input.label, err);
+        process::exit(1);
+    })
+}
+
+fn run(day: &Day, part: Option<u8>, sources: &[Source], format: Format) {
+    let reads: Vec<Option<&Source>> = if sources.is_empty() {
+        vec![None]
+    } else {
+        sources.iter().map(Some).collect()
+    };
+    for source in reads {
+        let _span = trace::span(&day.id().to_string());
+        run_input(day, part, &read_input(day, source), format);
+    }
+}
+
+fn run_input(day: &Day, part: Option<u8>, input: &Input, format: Format) {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let solved = parsed(day, input, (day.solve)(&input.contents, &parts));
+    if format != Format::Human {
+        solved.into_iter().for_each(|solved| {
+            println!(
+                "{}",
+                format.record(&Record::new(day.id(), &input.label, solved))
+            )
+        });
+        return;
+    }
+    println!("Day {} ({}) - {}", day.id().label(), day.title, input.label);
+    for solved in solved {
+        println!("  Part {}: {}", solved.part, solved.answer);
+    }
+}
+
+fn run_bench(day: &Day, config: &BenchConfig, source: Option<&Source>) -> Vec<BenchResult> {
+    let input = read_input(day, source);
+    let mut results = parsed(day, &input, (day.bench)(&input.contents, config));
+    results.extend(day.variants.iter().map(|variant| BenchResult {
+        name: format!("{} (part {})", variant.name, variant.part),
+        stats: bench::measure(config, || (variant.solve)(&input.contents)),
+    }));
+    results
+        .into_iter()
+        .map(|result| BenchResult {
+            name: format!("{} {}", day.id().crate_name(), result.name),
+            ..result
+        })
+        .collect()
+}
+
+fn run_profile(day: &Day, source: Option<&Source>) -> Vec<AllocProfile> {
+    let input = read_input(day, source);
+    let mut profiles = parsed(day, &input, (day.profile)(&input.contents));
+    profiles.extend(day.variants.iter().map(|variant| AllocProfile {
+        name: format!("{} (part {})", variant.name, variant.part),
+        stats: memory::measure(|| (variant.solve)(&input.contents)).1,
+    }));
+    profiles
+        .into_iter()
+        .map(|profile| AllocProfile {
+            name: format!("{} {}", day.id().crate_name(), profile.name),
+            ..profile
+        })
+        .collect()
+}
+
+fn load_answers(day: &Day) -> KnownAnswers {
+    KnownAnswers::load(&day.crate_dir()).unwrap_or_else(|err| {
+        eprintln!("error: couldn't load the answers of {}: {}", day.id(), err);
+        process::exit(1);
+    })
+}
+
+fn accept(day: &Day, part: Option<u8>) {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let input = read_input(day, None);
+    let mut answers = load_answers(day);
+    for solved in parsed(day, &input, (day.solve)(&input.contents, &parts)) {
+        println!(
+            "{} part {}: accepted {}",
+            day.id(),
+            solved.part,
+            solved.answer
+        );
+        answers.set(solved.part, solved.answer);
+    }
+    answers
+        .save(&day.crate_dir())
+        .expect("couldn't save the answers");
+}
+
+/// Solves both parts of `day` and compares them with the accepted answers. Returns whether no
+/// answer changed.
+fn verify(day: &Day) -> bool {
+    let input = read_input(day, None);
+    let answers = load_answers(day);
+    let mut all_match = true;
+    for solved in parsed(day, &input, (day.solve)(&input.contents, &[1, 2])) {
+        let verdict = match answers.check(solved.part, &solved.answer) {
+            Verdict::Match => "ok".to_string(),
+            Verdict::Unknown => "unknown, no accepted answer yet".to_string(),
+            Verdict::Changed { expected } => {
+                all_match = false;
+                format!("CHANGED, expected {}", expected)
+            }
+        };
+        println!(
+            "{} part {}: {} ({})",
+            day.id(),
+            solved.part,
+            solved.answer,
+            verdict
+        );
+    }
+    all_match
+}
+
+/// Runs every implementation of each part of `day` and prints how they compare. Returns
+/// whether they all agree.
+fn check(day: &Day, source: Option<&Source>) -> bool {
+    let input = read_input(day, source);
+    let checks = parsed(day, &input, check::check(day, &input.contents));
+    if checks.is_empty() {
+        println!("{}: no other implementations to check", day.id());
+        return true;
+    }
+    print!("{}", check::report(day, &checks));
+    checks.iter().all(check::PartCheck::agree)
+}
+
+fn load_config() -> FetchConfig {
+    FetchConfig::load().unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Submits `answer`, or else the one computed on the input of `day`, to `part`, or else the first
+/// part without an accepted answer. Returns whether it was right.
+fn submit(day: &Day, part: Option<u8>, answer: Option<String>) -> bool {
+    let part = match part.or_else(|| submit::next_part(&load_answers(day))) {
+        Some(part) => part,
+        None => {
+            eprintln!("error: both parts of {} are accepted already", day.id());
+            return false;
+        }
+    };
+    let answer = answer.unwrap_or_else(|| {
+        let input = read_input(day, None);
+        parsed(day, &input, (day.solve)(&input.contents, &[part]))
+            .remove(0)
+            .answer
+    });
+    let config = load_config();
+    match submit::submit(&config, day.id(), &day.crate_dir(), part, &answer) {
+        Ok(Outcome::Correct) => {
+            println!(
+                "{} part {}: {} is right, it's accepted now",
+                day.id(),
+                part,
+                answer
+            );
+            true
+        }
+        Ok(outcome @ Outcome::RateLimited { .. }) => {
+            println!(
+                "{} part {}: {} wasn't checked, {}",
+                day.id(),
+                part,
+                answer,
+                outcome
+            );
+            false
+        }
+        Ok(outcome) => {
+            println!("{} part {}: {} is {}", day.id(), part, answer, outcome);
+            false
+        }
+        Err(err) => {
+            eprintln!("error: {}: {}", day.id(), err);
+            false
+        }
+    }
+}
+
+/// Downloads the input of `day` unless it's cached already. Returns whether the input is there
+/// now.
+fn fetch(day: &Day, config: &FetchConfig) -> bool {
+    match fetch::fetch(config, day.id(), &day.crate_dir()) {
+        Ok(Fetched::Cached(path)) => {
+            println!("{}: already cached in {}", day.id(), path.display());
+            true
+        }
+        Ok(Fetched::Downloaded(path)) => {
+            println!("{}: downloaded to {}", day.id(), path.display());
+            true
+        }
+        Err(err) => {
+            eprintln!("error: {}: {}", day.id(), err);
+            false
+        }
+    }
+}
+
+/// Generates an input for `day`, written to `output` or else stdout, with its answers written to
+/// `answers` or else stderr. The seed goes to stderr too, to make the same input again.
+fn generate(day: PuzzleId, config: &GenConfig, output: Option<&str>, answers: Option<&str>) {
+    let generator = generate::generator(day).unwrap_or_else(|| {
+        eprintln!("error: {} has no input generator", day);
+        process::exit(1);
+    });
+    let seed = config.seed.unwrap_or_else(|| Rng::from_time().next_u64());
+    let generated = generator(&mut Rng::new(seed), config).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(2);
+    });
+    eprintln!("{}, size {}, seed {}", day, config.size, seed);
+    generated
+        .notes
+        .iter()
+        .for_each(|note| eprintln!("note: {}", note));
+    match output {
+        Some(path) => write(path, &generated.input).expect("couldn't write the input"),
+        None => print!("{}", generated.input),
+    }
+    match answers {
+        Some(path) => {
+            write(path, generated.answers.to_string()).expect("couldn't write the answers")
+        }
+        None => eprint!("{}", generated.answers),
+    }
+}
@@ -0,0 +1,589 @@
+use crate::days::{self, Day};
+use common::input::{Input, Source};
+use common::puzzle::{PuzzleId, DEFAULT_YEAR};
+use common::records::records;
+use common::Solution;
+use std::convert::TryFrom;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "commands:
+    load <day> [FILE]   load the input of a day, its default one unless FILE is given
+    run [PART]          solve the loaded input, both parts unless PART is given
+    show <N>            print line N of the loaded input
+    help                list the commands, with the ones of the loaded day
+    quit                leave (so does end of input)";
+
+/// A command the shell knows for one day only, next to the ones it knows for every day.
+struct Query {
+    name: &'static str,
+    usage: &'static str,
+    help: &'static str,
+    /// Runs the query with its arguments and the loaded input, when there is one.
+    run: fn(&[&str], Option<&str>) -> Result<String, String>,
+}
+
+/// The queries of a puzzle.
+fn queries(puzzle: PuzzleId) -> &'static [Query] {
+    match (puzzle.year, puzzle.day) {
+        (DEFAULT_YEAR, 1) => &[Query {
+            name: "find",
+            usage: "find <COUNT> [SUM]",
+            help: "the first COUNT entries summing to SUM (2020 by default)",
+            run: find_entries,
+        }],
+        (DEFAULT_YEAR, 2) => &[Query {
+            name: "password",
+            usage: "password <N|LINE>",
+            help: "check line N of the input, or LINE, against both policies",
+            run: check_password,
+        }],
+        (DEFAULT_YEAR, 3) => &[Query {
+            name: "slope",
+            usage: "slope <RIGHT> <DOWN>",
+            help: "count the trees hit going down the map with that slope",
+            run: ride_slope,
+        }],
+        (DEFAULT_YEAR, 4) => &[Query {
+            name: "passport",
+            usage: "passport <N|FIELDS>",
+            help: "validate passport N of the input, or FIELDS, field by field",
+            run: check_passport,
+        }],
+        (DEFAULT_YEAR, 5) => &[Query {
+            name: "decode",
+            usage: "decode <PASS>",
+            help: "decode a boarding pass step by step",
+            run: decode_pass,
+        }],
+        (DEFAULT_YEAR, 6) => &[Query {
+            name: "group",
+            usage: "group <N>",
+            help: "count the questions group N of the input answered",
+            run: count_group,
+        }],
+        _ => &[],
+    }
+}
+
+/// What the shell answers to a line.
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+/// The state of the shell: the day loaded, if any, and its input, if it could be read.
+#[derive(Default)]
+pub struct Session {
+    day: Option<&'static Day>,
+    input: Option<Input>,
+}
+
+impl Session {
+    pub fn prompt(&self) -> String {
+        match self.day {
+            Some(day) => format!("{}> ", day.id()),
+            None => "aoc> ".to_string(),
+        }
+    }
+
+    /// Runs one line. Errors are part of the output: the shell goes on after them.
+    pub fn execute(&mut self, line: &str) -> Reply {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let result = match words.split_first() {
+            None => Ok(String::new()),
+            Some((&("quit" | "exit"), _)) => return Reply::Quit,
+            Some((&"help", _)) => Ok(self.help()),
+            Some((&"load", args)) => self.load(args),
+            Some((&"run", args)) => self.run(args),
+            Some((&"show", args)) => self.show(args),
+            Some((command, args)) => self.query(command, args),
+        };
+        Reply::Output(result.unwrap_or_else(|err| format!("error: {}", err)))
+    }
+
+    fn help(&self) -> String {
+        let mut help = HELP.to_string();
+        if let Some(day) = self.day {
+            help.push_str(&format!("\n\n{}:", day.id()));
+            for query in queries(day.id()) {
+                help.push_str(&format!("\n    {:<20}{}", query.usage, query.help));
+            }
+        }
+        help
+    }
+
+    fn load(&mut self, args: &[&str]) -> Result<String, String> {
+        let (puzzle, file) = match args {
+            [puzzle] => (puzzle, None),
+            [puzzle, file] => (puzzle, Some(Source::from(*file))),
+            _ => return Err("usage: load <day> [FILE]".to_string()),
+        };
+        self.open(puzzle.parse()?, file)
+    }
+
+    /// Loads `puzzle` with the input from `file`, or its default one.
+    fn open(&mut self, puzzle: PuzzleId, file: Option<Source>) -> Result<String, String> {
+        if file == Some(Source::Stdin) {
+            return Err("the shell reads its commands from stdin, load a file instead".to_string());
+        }
+        let day = days::find(puzzle).ok_or_else(|| format!("{} is not solved yet", puzzle))?;
+        self.day = Some(day);
+        self.input = None;
+        let input = day
+            .read_input(file.as_ref())
+            .map_err(|err| format!("{} loaded without an input: {}", puzzle, err))?;
+        let loaded = format!(
+            "loaded {} ({}): {}, {} lines",
+            puzzle,
+            day.title,
+            input.label,
+            input.contents.lines().count()
+        );
+        self.input = Some(input);
+        Ok(loaded)
+    }
+
+    fn input(&self) -> Result<&str, String> {
+        match (&self.day, &self.input) {
+            (_, Some(input)) => Ok(&input.contents),
+            (Some(_), None) => Err("the day has no input, `load` one from a file".to_string()),
+            (None, None) => Err("no day is loaded, use `load <day>`".to_string()),
+        }
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, String> {
+        let parts = match args {
+            [] => vec![1, 2],
+            [part @ ("1" | "2")] => vec![part.parse().expect("the part is 1 or 2")],
+            _ => return Err("usage: run [1|2]".to_string()),
+        };
+        let input = self.input()?;
+        let day = self.day.expect("an input belongs to a day");
+        let solved = (day.solve)(input, &parts).map_err(|err| err.to_string())?;
+        Ok(solved
+            .iter()
+            .map(|solved| {
+                format!(
+                    "part {}: {} ({:?} parsing, {:?} solving)",
+                    solved.part, solved.answer, solved.parse_time, solved.solve_time
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn show(&self, args: &[&str]) -> Result<String, String> {
+        let number = match args {
+            [number] => number,
+            _ => return Err("usage: show <N>".to_string()),
+        };
+        nth(self.input()?.lines(), number, "line").map(String::from)
+    }
+
+    fn query(&self, command: &str, args: &[&str]) -> Result<String, String> {
+        let unknown = || format!("unknown command `{}`, see `help`", command);
+        let day = self.day.ok_or_else(unknown)?;
+        let query = queries(day.id())
+            .iter()
+            .find(|query| query.name == command)
+            .ok_or_else(unknown)?;
+        (query.run)(
+            args,
+            self.input.as_ref().map(|input| input.contents.as_str()),
+        )
+        .map_err(|err| match err.strip_prefix("usage") {
+            Some(_) => err,
+            None => format!("{}: {}", query.name, err),
+        })
+    }
+}
+
+/// The `number`th (from 1) of `items`, called `what` in errors.
+fn nth<'a>(
+    mut items: impl Iterator<Item = &'a str>,
+    number: &str,
+    what: &str,
+) -> Result<&'a str, String> {
+    let index: usize = number
+        .parse()
+        .ok()
+        .filter(|&index| index > 0)
+        .ok_or_else(|| format!("invalid {} number: {}", what, number))?;
+    items
+        .nth(index - 1)
+        .ok_or_else(|| format!("there is no {} {}", what, index))
+}
+
+fn needs_input(input: Option<&str>) -> Result<&str, String> {
+    input.ok_or_else(|| "this needs an input, `load` one".to_string())
+}
+
+/// The item numbered by a single argument, or the arguments themselves as a text.
+fn item_or_text<'a>(
+    args: &'a [&'a str],
+    input: Option<&'a str>,
+    items: impl FnOnce(&'a str) -> Vec<&'a str>,
+    what: &str,
+) -> Result<String, String> {
+    match args {
+        [] => Err(format!("which {}?", what)),
+        [number] if number.chars().all(|c| c.is_ascii_digit()) => {
+            nth(items(needs_input(input)?).into_iter(), number, what).map(String::from)
+        }
+        text => Ok(text.join(" ")),
+    }
+}
+
+fn verdict(valid: bool) -> &'static str {
+    if valid {
+        "valid"
+    } else {
+        "INVALID"
+    }
+}
+
+/// The first `count` entries, in input order, that sum to `sum`.
+fn combination(entries: &[i64], count: usize, sum: i64) -> Result<Option<Vec<i64>>, String> {
+    if count == 0 {
+        return Ok(if sum == 0 { Some(Vec::new()) } else { None });
+    }
+    for (index, &entry) in entries.iter().enumerate() {
+        let rest_sum = sum
+            .checked_sub(entry)
+            .ok_or_else(|| format!("{} - {} doesn't fit in 64 bits", sum, entry))?;
+        if let Some(mut rest) = combination(&entries[index + 1..], count - 1, rest_sum)? {
+            rest.insert(0, entry);
+            return Ok(Some(rest));
+        }
+    }
+    Ok(None)
+}
+
+fn find_entries(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let (count, sum) = match args {
+        [count] => (count.parse(), Ok(2020)),
+        [count, sum] => (count.parse(), sum.parse()),
+        _ => return Err("usage: find <COUNT> [SUM]".to_string()),
+    };
+    let (count, sum): (usize, i64) = match (count, sum) {
+        (Ok(count), Ok(sum)) if count > 0 => (count, sum),
+        _ => return Err("the count and the sum are numbers, the count at least 1".to_string()),
+    };
+    let input = needs_input(input)?;
+    let entries: Vec<i64> = day1::ExpenseReport::parse(input)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .map(i64::from)
+        .collect();
+    let found = combination(&entries, count, sum)?
+        .ok_or_else(|| format!("no {} entries sum to {}", count, sum))?;
+    let terms = found
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let product = found
+        .iter()
+        .try_fold(1i64, |product, &entry| product.checked_mul(entry))
+        .ok_or_else(|| {
+            format!(
+                "{} = {}, but their product doesn't fit in 64 bits",
+                terms, sum
+            )
+        })?;
+    Ok(format!("{} = {}, their product is {}", terms, sum, product))
+}
+
+fn check_password(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let line = item_or_text(args, input, |input| input.lines().collect(), "line")?;
+    let recovered: day2::RecoveredPassword = line.parse().map_err(|err| format!("{}", err))?;
+    let policy = &recovered.policy;
+    let at = |position: i32| {
+        usize::try_from(position - 1)
+            .ok()
+            .and_then(|index| recovered.password.chars().nth(index))
+            .map_or_else(|| "past the end".to_string(), |c| format!("{:?}", c))
+    };
+    Ok(format!(
+        "{}\npart 1: {:?} appears {} times, {} to {} are needed: {}\n\
+         part 2: position {} is {}, position {} is {}, exactly one {:?} is needed: {}",
+        line,
+        policy.char,
+        recovered.password.matches(policy.char).count(),
+        policy.min,
+        policy.max,
+        verdict(recovered.validate_part1()),
+        policy.min,
+        at(policy.min),
+        policy.max,
+        at(policy.max),
+        policy.char,
+        verdict(recovered.validate_part2()),
+    ))
+}
+
+fn ride_slope(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let (right, down) = match args {
+        [right, down] => match (right.parse(), down.parse()) {
+            (Ok(right), Ok(down)) if down > 0 => (right, down),
+            _ => return Err("RIGHT and DOWN are numbers, DOWN at least 1".to_string()),
+        },
+        _ => return Err("usage: slope <RIGHT> <DOWN>".to_string()),
+    };
+    let toboggan = day3::Toboggan::parse(needs_input(input)?).map_err(|err| err.to_string())?;
+    let trees = toboggan.count_trees(
+        day3::HorizontalMove::Right(right),
+        day3::VerticalMove::Down(down),
+    );
+    Ok(format!("right {}, down {}: {} trees", right, down, trees))
+}
+
+fn check_passport(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let text = item_or_text(
+        args,
+        input,
+        |input| records(input).map(|record| record.text()).collect(),
+        "passport",
+    )?;
+    let passport =
+        day4::CredentialsValidator::try_from(text.as_str()).map_err(|err| err.to_string())?;
+    let credentials = passport.credentials();
+    let mut lines = vec![text.replace('\n', " ")];
+    let missing = passport.missing_fields();
+    for (field, validate) in day4::Credentials::FIELD_VALIDATION_FUNCS.iter() {
+        let value = match *field {
+            _ if missing.contains(field) => "missing".to_string(),
+            "byr" => credentials.byr.to_string(),
+            "iyr" => credentials.iyr.to_string(),
+            "eyr" => credentials.eyr.to_string(),
+            "hgt" => credentials.hgt.to_string(),
+            "hcl" => credentials.hcl.to_string(),
+            "ecl" => credentials.ecl.to_string(),
+            _ => credentials.pid.to_string(),
+        };
+        lines.push(format!(
+            "  {}: {} ({})",
+            field,
+            verdict(validate(credentials)),
+            value
+        ));
+    }
+    lines.push(format!(
+        "part 1: {}{}",
+        verdict(passport.is_valid_credential()),
+        if missing.is_empty() {
+            String::new()
+        } else {
+            format!(", missing {}", missing.join(", "))
+        }
+    ));
+    let invalid = credentials.invalid_fields();
+    lines.push(format!(
+        "part 2: {}{}",
+        verdict(passport.is_valid()),
+        if invalid.is_empty() {
+            String::new()
+        } else {
+            format!(", failed {}", invalid.join(", "))
+        }
+    ));
+    Ok(lines.join("\n"))
+}
+
+fn decode_pass(args: &[&str], _input: Option<&str>) -> Result<String, String> {
+    let pass = match args {
+        [pass] => pass,
+        _ => return Err("usage: decode <PASS>".to_string()),
+    };
+    let seat: day5::SeatCode = pass.parse().map_err(|err| format!("{}", err))?;
+    let steps = |what: &str, steps: Vec<(char, i32, i32)>| {
+        steps
+            .into_iter()
+            .map(|(c, low, high)| format!("  {}: {} {} to {}", c, what, low, high))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    Ok(format!(
+        "{}\n{}\nrow {}, column {}, seat ID {}",
+        steps("rows", seat.row_steps()),
+        steps("columns", seat.column_steps()),
+        seat.find_row(),
+        seat.find_column(),
+        seat.seat_id()
+    ))
+}
+
+fn count_group(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let number = match args {
+        [number] => number,
+        _ => return Err("usage: group <N>".to_string()),
+    };
+    let input = needs_input(input)?;
+    let groups = day6::parse_input_lines(input).map_err(|err| err.to_string())?;
+    let text = nth(records(input).map(|record| record.text()), number, "group")?;
+    let group = &groups[number.parse::<usize>().expect("the group exists") - 1];
+    Ok(format!(
+        "{}\nanyone answered {} questions, everyone answered {}",
+        text,
+        group.unique_questions_answered(),
+        group.all_yes_question_count()
+    ))
+}
+
+/// Reads commands from stdin until `quit` or the end of the input, starting with `load` when
+/// a day is given.
+pub fn repl(day: Option<PuzzleId>, input: Option<Source>) {
+    let mut session = Session::default();
+    if let Some(day) = day {
+        match session.open(day, input) {
+            Ok(loaded) => println!("{}", loaded),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{}", session.prompt());
+        io::stdout().flush().expect("couldn't write to stdout");
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("error: couldn't read stdin: {}", err);
+                return;
+            }
+            None => {
+                println!();
+                return;
+            }
+        };
+        match session.execute(&line) {
+            Reply::Output(output) if output.is_empty() => {}
+            Reply::Output(output) => println!("{}", output),
+            Reply::Quit => return,
+        }
+    }
+}
+
+#[cfg(test)]
+fn output(session: &mut Session, line: &str) -> String {
+    match session.execute(line) {
+        Reply::Output(output) => output,
+        Reply::Quit => panic!("{} quit", line),
+    }
+}
+
+#[cfg(test)]
+fn loaded(day: u8, example: &str) -> Session {
+    let mut session = Session::default();
+    let example = days::find(PuzzleId::new(2020, day))
+        .unwrap()
+        .crate_dir()
+        .join(common::examples::EXAMPLES_DIR)
+        .join(example);
+    let reply = output(&mut session, &format!("load {} {}", day, example.display()));
+    assert!(
+        reply.starts_with(&format!("loaded day {}", day)),
+        "{}",
+        reply
+    );
+    session
+}
+
+#[test]
+fn test_session_basics() {
+    let mut session = Session::default();
+    assert_eq!(session.prompt(), "aoc> ");
+    assert_eq!(session.execute("quit"), Reply::Quit);
+    assert_eq!(output(&mut session, "   "), "");
+    assert_eq!(
+        output(&mut session, "run"),
+        "error: no day is loaded, use `load <day>`"
+    );
+    assert_eq!(
+        output(&mut session, "decode FBFBBFFRLR"),
+        "error: unknown command `decode`, see `help`"
+    );
+    assert!(output(&mut session, "load 26").starts_with("error: day 26 is not solved yet"));
+    assert!(!output(&mut session, "help").contains("decode"));
+
+    let mut session = loaded(5, "example.txt");
+    assert_eq!(session.prompt(), "day 5> ");
+    assert!(output(&mut session, "help").contains("decode <PASS>"));
+    assert!(output(&mut session, "run 1").starts_with("part 1: 820 ("));
+    assert_eq!(output(&mut session, "show 3"), "FFFBBBFRRR");
+    assert_eq!(output(&mut session, "show 5"), "error: there is no line 5");
+}
+
+#[test]
+fn test_queries() {
+    let mut day1 = loaded(1, "example.txt");
+    assert_eq!(
+        output(&mut day1, "find 2"),
+        "1721 + 299 = 2020, their product is 514579"
+    );
+    assert_eq!(
+        output(&mut day1, "find 2 1"),
+        "error: find: no 2 entries sum to 1"
+    );
+    let dir = common::scratch::ScratchDir::new("repl-find");
+    let big = dir.join("big.txt");
+    std::fs::write(&big, "2000000000\n2000000000\n2000000000\n").unwrap();
+    assert!(output(&mut day1, &format!("load 1 {}", big.display())).starts_with("loaded day 1"));
+    assert_eq!(
+        output(&mut day1, "find 3 6000000000"),
+        "error: find: 2000000000 + 2000000000 + 2000000000 = 6000000000, \
+         but their product doesn't fit in 64 bits"
+    );
+    assert_eq!(
+        output(&mut day1, "find 2 -9223372036854775808"),
+        "error: find: -9223372036854775808 - 2000000000 doesn't fit in 64 bits"
+    );
+
+    let mut day2 = loaded(2, "example.txt");
+    assert_eq!(
+        output(&mut day2, "password 2"),
+        "1-3 b: cdefg\npart 1: 'b' appears 0 times, 1 to 3 are needed: INVALID\n\
+         part 2: position 1 is 'c', position 3 is 'e', exactly one 'b' is needed: INVALID"
+    );
+    assert!(output(&mut day2, "password 2-9 c: ccccccccc")
+        .ends_with("position 2 is 'c', position 9 is 'c', exactly one 'c' is needed: INVALID"));
+
+    let mut day3 = loaded(3, "example.txt");
+    assert_eq!(output(&mut day3, "slope 3 1"), "right 3, down 1: 7 trees");
+    assert_eq!(
+        output(&mut day3, "slope 3"),
+        "error: usage: slope <RIGHT> <DOWN>"
+    );
+
+    let mut day4 = loaded(4, "example.txt");
+    let passport = output(&mut day4, "passport 2");
+    assert!(
+        passport.contains("  hgt: INVALID (missing)"),
+        "{}",
+        passport
+    );
+    assert!(passport.ends_with("part 1: INVALID, missing hgt\npart 2: INVALID, failed hgt"));
+    let passport = output(
+        &mut day4,
+        "passport pid:087499704 hgt:74inx ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+    );
+    assert!(passport.contains("  hgt: INVALID (74inx)"), "{}", passport);
+    assert!(passport.ends_with("part 1: valid\npart 2: INVALID, failed hgt"));
+
+    let mut day5 = loaded(5, "example.txt");
+    let decoded = output(&mut day5, "decode FBFBBFFRLR");
+    assert!(decoded.starts_with("  F: rows 0 to 63\n  B: rows 32 to 63\n"));
+    assert!(decoded.ends_with("  R: columns 5 to 5\nrow 44, column 5, seat ID 357"));
+    assert!(output(&mut day5, "decode FBFBBFFRXR").starts_with("error: decode: "));
+
+    let mut day6 = loaded(6, "example.txt");
+    assert_eq!(
+        output(&mut day6, "group 2"),
+        "a\nb\nc\nanyone answered 3 questions, everyone answered 0"
+    );
+    assert_eq!(
+        output(&mut day6, "group 9"),
+        "error: group: there is no group 9"
+    );
+}
@@ -0,0 +1,562 @@
+use common::puzzle::{self, PuzzleId, DEFAULT_YEAR};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Why a day couldn't be scaffolded.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day already has a directory or is already registered somewhere. Nothing is written.
+    Exists {
+        puzzle: PuzzleId,
+        path: PathBuf,
+    },
+    /// A file that should be edited doesn't look the way it's expected to.
+    Layout {
+        path: PathBuf,
+        message: &'static str,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists { puzzle, path } => {
+                write!(f, "{} already exists in {}", puzzle, path.display())
+            }
+            ScaffoldError::Layout { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The workspace files that list every day.
+struct Registry {
+    members: PathBuf,
+    runner_manifest: PathBuf,
+    days: PathBuf,
+}
+
+impl Registry {
+    fn new(workspace: &Path) -> Self {
+        Registry {
+            members: workspace.join("Cargo.toml"),
+            runner_manifest: workspace.join("aoc").join("Cargo.toml"),
+            days: workspace.join("aoc").join("src").join("days.rs"),
+        }
+    }
+}
+
+/// Creates the crate of `puzzle` in `workspace` and registers it: as a workspace member, as
+/// a dependency of the runner and in the runner's list of days. Returns the files it created or
+/// changed.
+///
+/// The runner lists the day as "Day N" unless it's given the `title` of the puzzle. Refuses to
+/// touch anything when the day already has a directory or is already registered.
+pub fn new_day(
+    workspace: &Path,
+    puzzle: PuzzleId,
+    title: Option<&str>,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let crate_dir = workspace.join(puzzle.crate_path());
+    if crate_dir.exists() {
+        return Err(ScaffoldError::Exists {
+            puzzle,
+            path: crate_dir,
+        });
+    }
+    // Crate paths always use `/`, whatever the platform.
+    let member = crate_member(puzzle);
+
+    let registry = Registry::new(workspace);
+    let members = add_line(
+        &registry.members,
+        &read(&registry.members)?,
+        puzzle,
+        |line| quoted_member(line.trim().trim_end_matches(',')),
+        &format!("    \"{}\",", member),
+    )?;
+    let runner_manifest = add_line(
+        &registry.runner_manifest,
+        &read(&registry.runner_manifest)?,
+        puzzle,
+        dependency_puzzle,
+        &format!("{} = {{ path = \"../{}\" }}", puzzle.crate_name(), member),
+    )?;
+    let days = register_day(
+        &registry.days,
+        &read(&registry.days)?,
+        puzzle,
+        title.unwrap_or(&format!("Day {}", puzzle.day)),
+    )?;
+
+    let files = [
+        (crate_dir.join("Cargo.toml"), manifest(puzzle)),
+        (crate_dir.join("src").join("lib.rs"), lib(puzzle, title)),
+        (crate_dir.join("src").join("main.rs"), main(puzzle)),
+        (
+            crate_dir.join("tests").join("examples.rs"),
+            examples(puzzle),
+        ),
+    ];
+    let mut written = Vec::new();
+    for (path, contents) in &files {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|source| ScaffoldError::Io {
+                path: dir.to_path_buf(),
+                source,
+            })?;
+        }
+        write(path, contents)?;
+        written.push(path.clone());
+    }
+    write(&registry.members, &members)?;
+    write(&registry.runner_manifest, &runner_manifest)?;
+    write(&registry.days, &days)?;
+    written.extend([registry.members, registry.runner_manifest, registry.days]);
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// The workspace member of `puzzle`: `dayN`, or `YEAR/dayN` outside of the default year.
+fn crate_member(puzzle: PuzzleId) -> String {
+    if puzzle.year == DEFAULT_YEAR {
+        format!("day{}", puzzle.day)
+    } else {
+        format!("{}/day{}", puzzle.year, puzzle.day)
+    }
+}
+
+/// The puzzle of a `"dayN"` or `"YEAR/dayN"` workspace member.
+fn quoted_member(member: &str) -> Option<PuzzleId> {
+    let member = member.strip_prefix('"')?.strip_suffix('"')?;
+    let (year, day) = match member.split_once('/') {
+        Some((year, day)) => (puzzle::parse_year(year).ok()?, day),
+        None => (DEFAULT_YEAR, member),
+    };
+    Some(PuzzleId::new(year, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// The puzzle of a `dayN = { path = "../dayN" }` or `dayN_YEAR = { path = "../YEAR/dayN" }`
+/// dependency.
+fn dependency_puzzle(line: &str) -> Option<PuzzleId> {
+    let (name, _) = line.split_once('=')?;
+    let name = name.trim().strip_prefix("day")?;
+    let (day, year) = match name.split_once('_') {
+        Some((day, year)) => (day, puzzle::parse_year(year).ok()?),
+        None => (name, DEFAULT_YEAR),
+    };
+    Some(PuzzleId::new(year, day.parse().ok()?))
+}
+
+/// Adds `new_line` to `contents` right after the last line of an earlier puzzle, as recognised
+/// by `puzzle_of`, so that days stay in order, and years too.
+fn add_line(
+    path: &Path,
+    contents: &str,
+    puzzle: PuzzleId,
+    puzzle_of: impl Fn(&str) -> Option<PuzzleId>,
+    new_line: &str,
+) -> Result<String, ScaffoldError> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let days: Vec<(usize, PuzzleId)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| puzzle_of(line).map(|day| (index, day)))
+        .collect();
+    if days.iter().any(|&(_, day)| day == puzzle) {
+        return Err(ScaffoldError::Exists {
+            puzzle,
+            path: path.to_path_buf(),
+        });
+    }
+    let after = match days.iter().rev().find(|&&(_, day)| day < puzzle) {
+        Some(&(index, _)) => index,
+        // Every listed day comes later, so the new one goes right before the first of them.
+        None => match days.first() {
+            Some(&(index, _)) => index.checked_sub(1).ok_or(ScaffoldError::Layout {
+                path: path.to_path_buf(),
+                message: "expected something before the first day",
+            })?,
+            None => {
+                return Err(ScaffoldError::Layout {
+                    path: path.to_path_buf(),
+                    message: "no day is listed, so there's no place to add one",
+                })
+            }
+        },
+    };
+    let mut result = lines[..=after].join("\n");
+    result.push('\n');
+    result.push_str(new_line);
+    result.push('\n');
+    for line in &lines[after + 1..] {
+        result.push_str(line);
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Adds `puzzle` to the `DAYS` array of the runner, keeping the array in order.
+fn register_day(
+    path: &Path,
+    contents: &str,
+    puzzle: PuzzleId,
+    title: &str,
+) -> Result<String, ScaffoldError> {
+    let layout = |message| ScaffoldError::Layout {
+        path: path.to_path_buf(),
+        message,
+    };
+    const DECLARATION: &str = "pub const DAYS: [Day; ";
+    let start = contents
+        .find(DECLARATION)
+        .ok_or_else(|| layout("no `DAYS` array"))?;
+    let count_start = start + DECLARATION.len();
+    let count_end = count_start
+        + contents[count_start..]
+            .find(']')
+            .ok_or_else(|| layout("the length of `DAYS` isn't closed"))?;
+    let count: usize = contents[count_start..count_end]
+        .parse()
+        .map_err(|_| layout("the length of `DAYS` isn't a number"))?;
+    let end = count_start
+        + contents[count_start..]
+            .find("\n];\n")
+            .ok_or_else(|| layout("`DAYS` doesn't end with `];` on its own line"))?;
+
+    const ENTRY: &str = "\n    Day {\n        year: ";
+    const NUMBER: &str = ",\n        number: ";
+    let mut insert_at = end;
+    let mut entry_start = contents[..end].find(ENTRY);
+    while let Some(at) = entry_start {
+        let year_at = at + ENTRY.len();
+        let year: u16 = leading_number(&contents[year_at..])
+            .ok_or_else(|| layout("a day in `DAYS` has no year"))?;
+        let number_at = year_at + year.to_string().len();
+        if !contents[number_at..].starts_with(NUMBER) {
+            return Err(layout("a day in `DAYS` has no number right after its year"));
+        }
+        let number = leading_number(&contents[number_at + NUMBER.len()..])
+            .ok_or_else(|| layout("a day in `DAYS` has no number"))?;
+        let day = PuzzleId::new(year, number);
+        if day == puzzle {
+            return Err(ScaffoldError::Exists {
+                puzzle,
+                path: path.to_path_buf(),
+            });
+        }
+        if day > puzzle && insert_at == end {
+            insert_at = at;
+        }
+        entry_start = contents[at + 1..end].find(ENTRY).map(|next| at + 1 + next);
+    }
+
+    let ty = format!("{}::Day{}", puzzle.crate_name(), puzzle.day);
+    let entry = format!(
+        "
+    Day {{
+        year: {},
+        number: {},
+        title: {:?},
+        solve: common::solve::<{}>,
+        bench: common::bench::bench_solution::<{}>,
+        profile: common::memory::profile_solution::<{}>,
+        variants: &[],
+    }},",
+        puzzle.year, puzzle.day, title, ty, ty, ty
+    );
+    Ok(format!(
+        "{}{}{}{}{}",
+        &contents[..count_start],
+        count + 1,
+        &contents[count_end..insert_at],
+        entry,
+        &contents[insert_at..]
+    ))
+}
+
+/// The number `text` starts with.
+fn leading_number<T: std::str::FromStr>(text: &str) -> Option<T> {
+    let digits: String = text.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+fn manifest(puzzle: PuzzleId) -> String {
+    // Days of other years are one directory deeper.
+    let common = if puzzle.year == DEFAULT_YEAR {
+        "../common"
+    } else {
+        "../../common"
+    };
+    format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+authors = ["Douglas Camata <d.camata@gmail.com>"]
+edition = "2018"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = {{ path = "{}" }}
+"#,
+        puzzle.crate_name(),
+        common
+    )
+}
+
+fn lib(puzzle: PuzzleId, title: Option<&str>) -> String {
+    let title = title.map_or_else(String::new, |title| format!(": {}", title));
+    let year = if puzzle.year == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!(" of {}", puzzle.year)
+    };
+    format!(
+        r#"//! Day {n}{year}{title}.
+
+use common::error::ParseError;
+use common::Solution;
+
+pub struct Day{n};
+
+impl Solution for Day{n} {{
+    type Parsed<'a> = Vec<&'a str>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {{
+        Ok(input.lines().collect())
+    }}
+
+    fn part1(lines: &Self::Parsed<'_>) -> Self::Answer {{
+        lines.len()
+    }}
+
+    fn part2(_lines: &Self::Parsed<'_>) -> Self::Answer {{
+        0
+    }}
+}}
+
+#[cfg(test)]
+const EXAMPLE: &str = "";
+
+#[test]
+#[ignore = "the example of the puzzle isn't filled in yet"]
+fn test_part1_example() {{
+    let parsed = Day{n}::parse(EXAMPLE).unwrap();
+    assert_eq!(Day{n}::part1(&parsed), 0);
+}}
+
+#[test]
+#[ignore = "the example of the puzzle isn't filled in yet"]
+fn test_part2_example() {{
+    let parsed = Day{n}::parse(EXAMPLE).unwrap();
+    assert_eq!(Day{n}::part2(&parsed), 0);
+}}
+"#,
+        n = puzzle.day,
+        year = year,
+        title = title
+    )
+}
+
+fn main(puzzle: PuzzleId) -> String {
+    format!(
+        r#"use common::puzzle::PuzzleId;
+use common::{{cli, Solution}};
+use {name}::Day{n};
+use std::path::Path;
+
+fn main() {{
+    let puzzle = PuzzleId::new({year}, {n});
+    cli::day_main::<Day{n}>(puzzle, Path::new(env!("CARGO_MANIFEST_DIR")), |input| {{
+        let parsed = Day{n}::parse(input)?;
+        println!("part 1: {{}}", Day{n}::part1(&parsed));
+        println!("part 2: {{}}", Day{n}::part2(&parsed));
+        Ok(())
+    }});
+}}
+"#,
+        name = puzzle.crate_name(),
+        year = puzzle.year,
+        n = puzzle.day
+    )
+}
+
+/// Runs the examples dropped in `examples/`, see `common::examples`.
+fn examples(puzzle: PuzzleId) -> String {
+    format!(
+        r#"use {name}::Day{n};
+use std::path::Path;
+
+#[test]
+fn test_examples() {{
+    common::examples::assert_examples::<Day{n}>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}}
+"#,
+        name = puzzle.crate_name(),
+        n = puzzle.day
+    )
+}
+
+#[cfg(test)]
+fn scratch_workspace(name: &str) -> common::scratch::ScratchDir {
+    let workspace = common::scratch::ScratchDir::new(&format!("new-day-{}", name));
+    fs::create_dir_all(workspace.join("aoc").join("src")).unwrap();
+    let real = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for file in &["Cargo.toml", "src/days.rs"] {
+        fs::copy(real.join(file), workspace.join("aoc").join(file)).unwrap();
+    }
+    fs::copy(real.join("../Cargo.toml"), workspace.join("Cargo.toml")).unwrap();
+    fs::create_dir_all(workspace.join("day6")).unwrap();
+    workspace
+}
+
+#[test]
+fn test_new_day() {
+    let workspace = scratch_workspace("new");
+    let written = new_day(&workspace, PuzzleId::new(2020, 7), Some("Handy Haversacks")).unwrap();
+    assert_eq!(written.len(), 7);
+
+    let lib = fs::read_to_string(workspace.join("day7/src/lib.rs")).unwrap();
+    assert!(lib.starts_with("//! Day 7: Handy Haversacks.\n"));
+    assert!(lib.contains("impl Solution for Day7 {"));
+    let main = fs::read_to_string(workspace.join("day7/src/main.rs")).unwrap();
+    assert!(main.contains("let puzzle = PuzzleId::new(2020, 7);"));
+
+    let members = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    assert!(members.contains("    \"day6\",\n    \"day7\",\n]"));
+    let manifest = fs::read_to_string(workspace.join("aoc/Cargo.toml")).unwrap();
+    assert!(manifest.contains("day6 = { path = \"../day6\" }\nday7 = { path = \"../day7\" }\n"));
+    let days = fs::read_to_string(workspace.join("aoc/src/days.rs")).unwrap();
+    assert!(days.contains("pub const DAYS: [Day; 7] = ["));
+    assert!(days.contains(
+        "        variants: &[],\n    },\n    Day {\n        year: 2020,\n        number: 7,\n        title: \"Handy Haversacks\",\n        solve: common::solve::<day7::Day7>,"
+    ));
+    assert!(days.ends_with("    },\n];\n\npub fn find(puzzle: PuzzleId) -> Option<&'static Day> {\n    DAYS.iter().find(|day| day.id() == puzzle)\n}\n"));
+}
+
+#[test]
+fn test_new_day_keeps_days_in_order() {
+    let workspace = scratch_workspace("order");
+    let days = workspace.join("aoc/src/days.rs");
+    let original = fs::read_to_string(&days).unwrap();
+    // Pretend day 5 was never registered to add it back in the middle.
+    let registered = register_day(&days, &original, PuzzleId::new(2020, 7), "Later").unwrap();
+    let without_5 = registered.replace("number: 5,", "number: 50,");
+    let readded = register_day(&days, &without_5, PuzzleId::new(2020, 5), "Again").unwrap();
+    let position = |number: &str| readded.find(number).unwrap();
+    assert!(position("number: 4,") < position("number: 5,"));
+    assert!(position("number: 5,") < position("number: 50,"));
+    assert!(readded.contains("pub const DAYS: [Day; 8] = ["));
+
+    let members = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day2\",\n]\n";
+    assert_eq!(
+        add_line(
+            &days,
+            members,
+            PuzzleId::new(2020, 1),
+            |line| quoted_member(line.trim().trim_end_matches(',')),
+            "    \"day1\","
+        )
+        .unwrap(),
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\n"
+    );
+}
+
+#[test]
+fn test_new_day_of_another_year() {
+    let workspace = scratch_workspace("year");
+    new_day(&workspace, PuzzleId::new(2021, 1), Some("Sonar Sweep")).unwrap();
+    new_day(&workspace, PuzzleId::new(2021, 3), None).unwrap();
+    // Goes between the last day of 2020 and the first one of 2021.
+    new_day(&workspace, PuzzleId::new(2020, 7), None).unwrap();
+
+    let manifest = fs::read_to_string(workspace.join("2021/day1/Cargo.toml")).unwrap();
+    assert!(manifest.contains("name = \"day1_2021\""));
+    assert!(manifest.contains("common = { path = \"../../common\" }"));
+    let lib = fs::read_to_string(workspace.join("2021/day1/src/lib.rs")).unwrap();
+    assert!(lib.starts_with("//! Day 1 of 2021: Sonar Sweep.\n"));
+    let main = fs::read_to_string(workspace.join("2021/day1/src/main.rs")).unwrap();
+    assert!(main.contains("use day1_2021::Day1;"));
+    assert!(main.contains("let puzzle = PuzzleId::new(2021, 1);"));
+
+    let members = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    assert!(
+        members.contains("    \"day6\",\n    \"day7\",\n    \"2021/day1\",\n    \"2021/day3\",\n]")
+    );
+    let manifest = fs::read_to_string(workspace.join("aoc/Cargo.toml")).unwrap();
+    assert!(manifest.contains(
+        "day7 = { path = \"../day7\" }\nday1_2021 = { path = \"../2021/day1\" }\nday3_2021 = { path = \"../2021/day3\" }\n"
+    ));
+    let days = fs::read_to_string(workspace.join("aoc/src/days.rs")).unwrap();
+    assert!(days.contains("pub const DAYS: [Day; 9] = ["));
+    let position = |entry: &str| days.find(entry).unwrap();
+    assert!(
+        position("year: 2020,\n        number: 7,") < position("year: 2021,\n        number: 1,")
+    );
+    assert!(
+        position("year: 2021,\n        number: 1,") < position("year: 2021,\n        number: 3,")
+    );
+    assert!(days.contains("solve: common::solve::<day1_2021::Day1>,"));
+
+    match new_day(&workspace, PuzzleId::new(2021, 1), None) {
+        Err(err) => assert!(err.to_string().starts_with("2021 day 1 already exists in ")),
+        other => panic!("expected the day to exist, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_new_day_refuses_to_overwrite() {
+    let workspace = scratch_workspace("exists");
+    let before = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    match new_day(&workspace, PuzzleId::new(2020, 6), None) {
+        Err(ScaffoldError::Exists { puzzle, path }) if puzzle.day == 6 => {
+            assert_eq!(path, workspace.join("day6"))
+        }
+        other => panic!("expected the day to exist, got {:?}", other),
+    }
+    // Registered without a directory, like a day whose crate was deleted.
+    match new_day(&workspace, PuzzleId::new(2020, 5), None) {
+        Err(ScaffoldError::Exists { puzzle, path }) if puzzle.day == 5 => {
+            assert_eq!(path, workspace.join("Cargo.toml"))
+        }
+        other => panic!("expected the day to exist, got {:?}", other),
+    }
+    assert!(!workspace.join("day5").exists());
+    assert_eq!(
+        fs::read_to_string(workspace.join("Cargo.toml")).unwrap(),
+        before
+    );
+}
@@ -0,0 +1,536 @@
+use crate::fetch::{self, FetchConfig, FetchError};
+use common::answers::KnownAnswers;
+use common::puzzle::PuzzleId;
+use common::NO_ANSWER;
+use std::error::Error;
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The file, inside each day's crate, where answers the website turned down are kept.
+pub const WRONG_ANSWERS_FILE: &str = "wrong_answers";
+
+/// What the website said about an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer wasn't looked at because another one was submitted too recently, with how
+    /// long is left to wait when the website says.
+    RateLimited {
+        wait: Option<String>,
+    },
+}
+
+impl Outcome {
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "right"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, {} left to wait", wait)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The solution found no answer, there is nothing worth sending.
+    NoAnswer {
+        part: u8,
+    },
+    /// The part was solved already, the website won't take another answer.
+    AlreadyAccepted {
+        part: u8,
+        accepted: String,
+    },
+    /// `answer` is `wrong`, or past a `wrong` one that was too high or too low, so it isn't
+    /// sent again.
+    RuledOut {
+        part: u8,
+        answer: String,
+        wrong: String,
+        outcome: Outcome,
+    },
+    /// The website answered something that isn't any [`Outcome`].
+    Unrecognized {
+        message: String,
+    },
+    Request(FetchError),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::NoAnswer { part } => write!(f, "part {} has no answer to submit", part),
+            SubmitError::AlreadyAccepted { part, accepted } => {
+                write!(f, "part {} was already accepted with {}", part, accepted)
+            }
+            SubmitError::RuledOut {
+                part,
+                answer,
+                wrong,
+                outcome,
+            } if answer == wrong => write!(
+                f,
+                "part {}: {} was already submitted and it was {}",
+                part, answer, outcome
+            ),
+            SubmitError::RuledOut {
+                part,
+                answer,
+                wrong,
+                outcome,
+            } => write!(
+                f,
+                "part {}: {} can't be right, {} was {}",
+                part, answer, wrong, outcome
+            ),
+            SubmitError::Unrecognized { message } => {
+                write!(f, "unexpected answer from the website: {}", message)
+            }
+            SubmitError::Request(err) => write!(f, "{}", err),
+            SubmitError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubmitError::Request(err) => Some(err),
+            SubmitError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<FetchError> for SubmitError {
+    fn from(err: FetchError) -> Self {
+        SubmitError::Request(err)
+    }
+}
+
+/// The answers of a day that were submitted and turned down, stored as one `partN: answer`
+/// line each, followed by `(too high)` or `(too low)` when the website said so.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WrongAnswers {
+    answers: Vec<(u8, String, Outcome)>,
+}
+
+impl WrongAnswers {
+    pub fn path(crate_dir: &Path) -> PathBuf {
+        crate_dir.join(WRONG_ANSWERS_FILE)
+    }
+
+    /// Loads the wrong answers of the crate at `crate_dir`. A missing file means there are none.
+    pub fn load(crate_dir: &Path) -> io::Result<Self> {
+        let path = Self::path(crate_dir);
+        match read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, crate_dir: &Path) -> io::Result<()> {
+        write(Self::path(crate_dir), self.to_string())
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut wrong = Self::default();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, answer) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {}: expected `partN: answer`", number + 1))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                key => return Err(format!("line {}: unknown key {:?}", number + 1, key)),
+            };
+            let answer = answer.trim();
+            let (answer, outcome) = match answer.split_once(" (") {
+                Some((answer, "too high)")) => (answer, Outcome::TooHigh),
+                Some((answer, "too low)")) => (answer, Outcome::TooLow),
+                _ => (answer, Outcome::Wrong),
+            };
+            wrong.add(part, answer.to_string(), outcome);
+        }
+        Ok(wrong)
+    }
+
+    pub fn add(&mut self, part: u8, answer: String, outcome: Outcome) {
+        assert!(outcome.is_wrong(), "{} isn't a wrong answer", outcome);
+        self.answers.push((part, answer, outcome));
+    }
+
+    /// The wrong answer of `part` that rules `answer` out, with what the website said about it:
+    /// `answer` itself, or a number that was too high (or too low) and isn't above (or below)
+    /// it.
+    pub fn ruling_out(&self, part: u8, answer: &str) -> Option<(&str, &Outcome)> {
+        let number = answer.parse::<i64>().ok();
+        self.answers
+            .iter()
+            .filter(|(wrong_part, _, _)| *wrong_part == part)
+            .find(|(_, wrong, outcome)| {
+                let bound = wrong.parse::<i64>().ok();
+                wrong == answer
+                    || match (outcome, number, bound) {
+                        (Outcome::TooHigh, Some(number), Some(bound)) => number >= bound,
+                        (Outcome::TooLow, Some(number), Some(bound)) => number <= bound,
+                        _ => false,
+                    }
+            })
+            .map(|(_, wrong, outcome)| (wrong.as_str(), outcome))
+    }
+}
+
+impl fmt::Display for WrongAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (part, answer, outcome) in &self.answers {
+            match outcome {
+                Outcome::TooHigh | Outcome::TooLow => {
+                    writeln!(f, "part{}: {} ({})", part, answer, outcome)?
+                }
+                _ => writeln!(f, "part{}: {}", part, answer)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The first part without an accepted answer, which is the one the website expects.
+pub fn next_part(accepted: &KnownAnswers) -> Option<u8> {
+    (1..=2).find(|&part| accepted.get(part).is_none())
+}
+
+/// The text of the message in the page the website answers with, without its markup.
+fn message(page: &str) -> String {
+    // Past `<article`, the rest of its opening tag comes first.
+    let (article, mut in_tag) = match page.split_once("<article") {
+        Some((_, rest)) => (rest.split("</article>").next().unwrap_or(rest), true),
+        None => (page, false),
+    };
+    let mut text = String::new();
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads what the website said about an answer out of the page it answered with.
+pub fn parse_response(page: &str) -> Result<Outcome, SubmitError> {
+    let message = message(page);
+    if message.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if message.contains("not the right answer") {
+        if message.contains("too high") {
+            Ok(Outcome::TooHigh)
+        } else if message.contains("too low") {
+            Ok(Outcome::TooLow)
+        } else {
+            Ok(Outcome::Wrong)
+        }
+    } else if message.contains("answer too recently") {
+        let wait = message
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+        Ok(Outcome::RateLimited { wait })
+    } else {
+        Err(SubmitError::Unrecognized { message })
+    }
+}
+
+/// Submits `answer` to `part` of `puzzle`, unless the accepted and wrong answers in `crate_dir`
+/// tell what the website would say already. A right answer is recorded as accepted and a
+/// wrong one as wrong, so it's never sent again.
+pub fn submit(
+    config: &FetchConfig,
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome, SubmitError> {
+    if answer.trim().is_empty() || answer == NO_ANSWER {
+        return Err(SubmitError::NoAnswer { part });
+    }
+    let io_error = |path: PathBuf| move |source| SubmitError::Io { path, source };
+    let mut accepted =
+        KnownAnswers::load(crate_dir).map_err(io_error(KnownAnswers::path(crate_dir)))?;
+    if let Some(accepted) = accepted.get(part) {
+        return Err(SubmitError::AlreadyAccepted {
+            part,
+            accepted: accepted.to_string(),
+        });
+    }
+    let mut wrong =
+        WrongAnswers::load(crate_dir).map_err(io_error(WrongAnswers::path(crate_dir)))?;
+    if let Some((ruled_out_by, outcome)) = wrong.ruling_out(part, answer) {
+        return Err(SubmitError::RuledOut {
+            part,
+            answer: answer.to_string(),
+            wrong: ruled_out_by.to_string(),
+            outcome: outcome.clone(),
+        });
+    }
+
+    let session = config.session()?;
+    let url = config.answer_url(puzzle);
+    let page = config
+        .agent()
+        .post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|err| fetch::request_error(url.clone(), err))?
+        .into_string()
+        .map_err(|err| FetchError::Http {
+            url,
+            message: err.to_string(),
+        })?;
+
+    let outcome = parse_response(&page)?;
+    if outcome == Outcome::Correct {
+        accepted.set(part, answer.to_string());
+        accepted
+            .save(crate_dir)
+            .map_err(io_error(KnownAnswers::path(crate_dir)))?;
+    } else if outcome.is_wrong() {
+        wrong.add(part, answer.to_string(), outcome.clone());
+        wrong
+            .save(crate_dir)
+            .map_err(io_error(WrongAnswers::path(crate_dir)))?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> common::scratch::ScratchDir {
+    common::scratch::ScratchDir::new(&format!("submit-{}", name))
+}
+
+/// A page like the ones the website answers with, around `message`.
+#[cfg(test)]
+fn page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><title>Day 1 - Advent of Code 2020</title></head><body>\n<main>\n<article><p>{}</p></article>\n</main>\n</body></html>\n",
+        message
+    )
+}
+
+#[test]
+fn test_parse_response() {
+    let outcome = |message: &str| parse_response(&page(message)).ok();
+    assert_eq!(
+        outcome("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to saving your vacation. <a href=\"/2020/day/1#part2\">[Continue to Part Two]</a>"),
+        Some(Outcome::Correct)
+    );
+    assert_eq!(
+        outcome("That's not the right answer.  If you're stuck, make sure you're using the full input data. <a href=\"/2020/day/1\">[Return to Day 1]</a>"),
+        Some(Outcome::Wrong)
+    );
+    assert_eq!(
+        outcome("That's not the right answer; your answer is too high.  Please wait one minute before trying again."),
+        Some(Outcome::TooHigh)
+    );
+    assert_eq!(
+        outcome("That's not the right answer; your answer is too low."),
+        Some(Outcome::TooLow)
+    );
+    assert_eq!(
+        outcome("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. <a href=\"/2020/day/1\">[Return to Day 1]</a>"),
+        Some(Outcome::RateLimited {
+            wait: Some("4m 32s".to_string())
+        })
+    );
+    assert_eq!(
+        parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        ))
+        .unwrap_err()
+        .to_string(),
+        "unexpected answer from the website: You don't seem to be solving the right level. Did you already complete it?"
+    );
+}
+
+#[test]
+fn test_wrong_answers() {
+    let mut wrong = WrongAnswers::parse("part1: 1500 (too high)\npart1: 12\npart2: abc\n").unwrap();
+    wrong.add(2, "40".to_string(), Outcome::TooLow);
+    assert_eq!(
+        wrong.to_string(),
+        "part1: 1500 (too high)\npart1: 12\npart2: abc\npart2: 40 (too low)\n"
+    );
+    assert_eq!(WrongAnswers::parse(&wrong.to_string()), Ok(wrong.clone()));
+
+    assert_eq!(wrong.ruling_out(1, "12"), Some(("12", &Outcome::Wrong)));
+    assert_eq!(
+        wrong.ruling_out(1, "1501"),
+        Some(("1500", &Outcome::TooHigh))
+    );
+    assert_eq!(wrong.ruling_out(1, "1499"), None);
+    assert_eq!(wrong.ruling_out(2, "40"), Some(("40", &Outcome::TooLow)));
+    assert_eq!(wrong.ruling_out(2, "-3"), Some(("40", &Outcome::TooLow)));
+    assert_eq!(wrong.ruling_out(2, "1500"), None);
+    assert!(WrongAnswers::parse("part3: 1").is_err());
+    assert_eq!(
+        WrongAnswers::parse("part2 : 40 (too low)"),
+        WrongAnswers::parse("part2: 40 (too low)")
+    );
+}
+
+#[test]
+fn test_submit_records_the_right_answer() {
+    let crate_dir = scratch_dir("right");
+    let (base_url, server) = fetch::stub_server("200 OK", &page("That's the right answer!"));
+    let config = fetch::config(&base_url, Some("cookie"));
+
+    let outcome = submit(&config, PuzzleId::new(2020, 1), &crate_dir, 1, "514579").unwrap();
+    assert_eq!(outcome, Outcome::Correct);
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /2020/day/1/answer HTTP/1.1\r\n"));
+    assert!(request.contains("session=cookie"));
+    assert!(request.ends_with("\r\n\r\nlevel=1&answer=514579"));
+    let accepted = KnownAnswers::load(&crate_dir).unwrap();
+    assert_eq!(accepted.get(1), Some("514579"));
+    assert_eq!(next_part(&accepted), Some(2));
+
+    // Nothing listens there anymore, so anything sent would fail differently.
+    let err = submit(&config, PuzzleId::new(2020, 1), &crate_dir, 1, "1").unwrap_err();
+    assert_eq!(err.to_string(), "part 1 was already accepted with 514579");
+}
+
+#[test]
+fn test_submit_never_resends_a_wrong_answer() {
+    let crate_dir = scratch_dir("wrong");
+    let (base_url, server) = fetch::stub_server(
+        "200 OK",
+        &page("That's not the right answer; your answer is too high."),
+    );
+    let config = fetch::config(&base_url, Some("cookie"));
+
+    let outcome = submit(&config, PuzzleId::new(2020, 5), &crate_dir, 2, "900").unwrap();
+    assert_eq!(outcome, Outcome::TooHigh);
+    server.join().unwrap();
+    assert_eq!(
+        read_to_string(WrongAnswers::path(&crate_dir)).unwrap(),
+        "part2: 900 (too high)\n"
+    );
+    assert!(!KnownAnswers::path(&crate_dir).exists());
+
+    // The stub serves a single request: if one of the refused answers was sent, it would get it
+    // instead of 727.
+    let (base_url, server) = fetch::stub_server("200 OK", &page("That's the right answer!"));
+    let config = fetch::config(&base_url, Some("cookie"));
+    let err = submit(&config, PuzzleId::new(2020, 5), &crate_dir, 2, "900").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "part 2: 900 was already submitted and it was too high"
+    );
+    let err = submit(&config, PuzzleId::new(2020, 5), &crate_dir, 2, "950").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "part 2: 950 can't be right, 900 was too high"
+    );
+    let outcome = submit(&config, PuzzleId::new(2020, 5), &crate_dir, 2, "727").unwrap();
+    assert_eq!(outcome, Outcome::Correct);
+    assert!(server
+        .join()
+        .unwrap()
+        .ends_with("\r\n\r\nlevel=2&answer=727"));
+}
+
+#[test]
+fn test_submit_refuses_no_answer() {
+    let crate_dir = scratch_dir("none");
+    // The stub serves a single request: if the missing answer was sent, it would get it instead
+    // of 1019371.
+    let (base_url, server) = fetch::stub_server("200 OK", &page("That's the right answer!"));
+    let config = fetch::config(&base_url, Some("cookie"));
+
+    for answer in &["none", ""] {
+        let err = submit(&config, PuzzleId::new(2020, 1), &crate_dir, 1, answer).unwrap_err();
+        assert_eq!(err.to_string(), "part 1 has no answer to submit");
+    }
+    assert!(!WrongAnswers::path(&crate_dir).exists());
+
+    let outcome = submit(&config, PuzzleId::new(2020, 1), &crate_dir, 1, "1019371").unwrap();
+    assert_eq!(outcome, Outcome::Correct);
+    assert!(server
+        .join()
+        .unwrap()
+        .ends_with("\r\n\r\nlevel=1&answer=1019371"));
+}
+
+#[test]
+fn test_submit_rate_limited_records_nothing() {
+    let crate_dir = scratch_dir("rate");
+    let (base_url, server) = fetch::stub_server(
+        "200 OK",
+        &page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait."),
+    );
+
+    let outcome = submit(
+        &fetch::config(&base_url, Some("cookie")),
+        PuzzleId::new(2020, 6),
+        &crate_dir,
+        1,
+        "6532",
+    )
+    .unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::RateLimited {
+            wait: Some("37s".to_string())
+        }
+    );
+    assert_eq!(outcome.to_string(), "rate limited, 37s left to wait");
+    server.join().unwrap();
+    assert!(!KnownAnswers::path(&crate_dir).exists());
+    assert!(!WrongAnswers::path(&crate_dir).exists());
+}
+
+#[test]
+fn test_submit_needs_a_token() {
+    let crate_dir = scratch_dir("token");
+    let err = submit(
+        &fetch::config("http://127.0.0.1:9", None),
+        PuzzleId::new(2020, 1),
+        &crate_dir,
+        1,
+        "514579",
+    )
+    .unwrap_err();
+    assert!(err.to_string().starts_with("no session token"));
+}
@@ -0,0 +1,243 @@
+use crate::days::Day;
+use common::answers::{KnownAnswers, Verdict};
+use common::fuzz::catch_quietly;
+use common::puzzle::PuzzleId;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// How an answer compares to the accepted one, or why there is no answer.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Match,
+    Changed {
+        expected: String,
+    },
+    /// There is no accepted answer to compare with.
+    Unknown,
+    /// The input couldn't be read or parsed.
+    Failed(String),
+    Panicked(String),
+}
+
+impl Status {
+    /// Whether this status should fail the run.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Match | Status::Unknown)
+    }
+}
+
+/// One line of the summary: a part of a day.
+#[derive(Debug)]
+pub struct Row {
+    pub day: PuzzleId,
+    pub part: u8,
+    pub title: &'static str,
+    pub answer: Option<String>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+    pub status: Status,
+}
+
+/// Runs `work` on every task with `jobs` worker threads and returns the results in the order of
+/// the tasks. A task that panics gives the panic message and doesn't stop the others.
+pub fn run_parallel<T, R>(
+    tasks: Vec<T>,
+    jobs: usize,
+    work: impl Fn(T) -> R + Sync,
+) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+{
+    let count = tasks.len();
+    let queue = Mutex::new(tasks.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(count));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().next();
+                let (index, task) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                // The panics are reported in the summary, the default hook would print them in
+                // the middle of it.
+                let result = catch_quietly(|| work(task));
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// A row of `day` without an answer yet.
+fn empty_row(day: &Day, part: u8, status: Status) -> Row {
+    Row {
+        day: day.id(),
+        part,
+        title: day.title,
+        answer: None,
+        parse_time: None,
+        solve_time: None,
+        status,
+    }
+}
+
+/// Solves both parts of `day` on its default input, which is read and parsed once, and compares
+/// them with the accepted answers.
+fn solve_day(day: &Day) -> Vec<Row> {
+    let solved = day
+        .read_input(None)
+        .map_err(|err| err.to_string())
+        .and_then(|input| {
+            (day.solve)(&input.contents, &[1, 2]).map_err(|err| format!("{}: {}", input.label, err))
+        });
+    let solved = match solved {
+        Ok(solved) => solved,
+        Err(err) => {
+            return [1, 2]
+                .iter()
+                .map(|&part| empty_row(day, part, Status::Failed(err.clone())))
+                .collect()
+        }
+    };
+    let answers = KnownAnswers::load(&day.crate_dir());
+    solved
+        .into_iter()
+        .map(|solved| {
+            let status = match &answers {
+                Ok(answers) => match answers.check(solved.part, &solved.answer) {
+                    Verdict::Match => Status::Match,
+                    Verdict::Changed { expected } => Status::Changed { expected },
+                    Verdict::Unknown => Status::Unknown,
+                },
+                Err(err) => Status::Failed(format!("couldn't load the answers: {}", err)),
+            };
+            Row {
+                answer: Some(solved.answer),
+                parse_time: Some(solved.parse_time),
+                solve_time: Some(solved.solve_time),
+                ..empty_row(day, solved.part, status)
+            }
+        })
+        .collect()
+}
+
+/// Solves both parts of every day in `days` on `jobs` threads. A day that panics gets the panic
+/// on both of its parts.
+pub fn summarize(days: &[&'static Day], jobs: usize) -> Vec<Row> {
+    let results = run_parallel(days.to_vec(), jobs, solve_day);
+    days.iter()
+        .zip(results)
+        .flat_map(|(&day, result)| {
+            result.unwrap_or_else(|message| {
+                [1, 2]
+                    .iter()
+                    .map(|&part| empty_row(day, part, Status::Panicked(message.clone())))
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// Formats the rows as an aligned table.
+pub fn table(rows: &[Row]) -> String {
+    let cells: Vec<[String; 7]> = rows
+        .iter()
+        .map(|row| {
+            let status = match &row.status {
+                Status::Match => "ok".to_string(),
+                Status::Unknown => "unknown".to_string(),
+                Status::Changed { expected } => format!("CHANGED, expected {}", expected),
+                Status::Failed(err) => format!("FAILED: {}", err),
+                Status::Panicked(message) => format!("PANICKED: {}", message),
+            };
+            let time =
+                |time: Option<Duration>| time.map_or("-".to_string(), |t| format!("{:.2?}", t));
+            [
+                row.day.label(),
+                row.part.to_string(),
+                row.title.to_string(),
+                row.answer.clone().unwrap_or_else(|| "-".to_string()),
+                time(row.parse_time),
+                time(row.solve_time),
+                status,
+            ]
+        })
+        .collect();
+    let header = ["day", "part", "title", "answer", "parse", "solve", "status"];
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(header[column].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: Vec<&str>| {
+        let mut line = String::new();
+        for (column, cell) in cells.iter().enumerate() {
+            let padding = " ".repeat(widths[column] - cell.chars().count());
+            match column {
+                // Numbers and durations are right-aligned, the rest left-aligned.
+                0 | 1 | 3 | 4 | 5 => line.push_str(&format!("{}{}  ", padding, cell)),
+                _ => line.push_str(&format!("{}{}  ", cell, padding)),
+            }
+        }
+        format!("{}\n", line.trim_end())
+    };
+    let mut table = line(header.to_vec());
+    for row in &cells {
+        table.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    table
+}
+
+#[test]
+fn test_run_parallel_keeps_order_and_catches_panics() {
+    let results = run_parallel((0..20).collect(), 4, |n: u32| {
+        if n % 7 == 3 {
+            panic!("task {} failed", n);
+        }
+        n * 2
+    });
+    assert_eq!(results.len(), 20);
+    assert_eq!(results[0], Ok(0));
+    assert_eq!(results[3], Err("task 3 failed".to_string()));
+    assert_eq!(results[10], Err("task 10 failed".to_string()));
+    assert_eq!(results[19], Ok(38));
+
+    let results = run_parallel(vec!["a"], 8, |s: &str| -> usize { panic!("{}", s) });
+    assert_eq!(results, vec![Err("a".to_string())]);
+    assert!(run_parallel(Vec::<u8>::new(), 4, |n| n).is_empty());
+}
+
+#[test]
+fn test_table() {
+    let row = |part, answer: Option<&str>, status| Row {
+        day: PuzzleId::new(2020, 4),
+        part,
+        title: "Passport Processing",
+        answer: answer.map(String::from),
+        parse_time: answer.map(|_| Duration::from_micros(1500)),
+        solve_time: answer.map(|_| Duration::from_micros(20)),
+        status,
+    };
+    let table = table(&[
+        row(1, Some("235"), Status::Match),
+        row(2, None, Status::Panicked("boom".to_string())),
+    ]);
+    assert_eq!(
+        table,
+        "day  part  title                answer   parse    solve  status\n\
+         \x20 4     1  Passport Processing     235  1.50ms  20.00µs  ok\n\
+         \x20 4     2  Passport Processing       -       -        -  PANICKED: boom\n"
+    );
+}
@@ -0,0 +1,257 @@
+use crate::days::Day;
+use common::examples::EXAMPLES_DIR;
+use common::input::{self, INPUT_FILE};
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// When every watched file was last modified.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Part numbers and their answers.
+pub type Answers = BTreeMap<u8, String>;
+
+/// Takes a snapshot of `paths`, walking into directories. Paths that don't exist are left out,
+/// so they show up as changed once they are created.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        walk(path, &mut snapshot);
+    }
+    snapshot
+}
+
+fn walk(path: &Path, snapshot: &mut Snapshot) {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return,
+    };
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                walk(&entry.path(), snapshot);
+            }
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// The files that were created, modified or removed between two snapshots.
+pub fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let modified = after
+        .iter()
+        .filter(|&(path, time)| before.get(path) != Some(time))
+        .map(|(path, _)| path.clone());
+    let removed = before
+        .keys()
+        .filter(|path| !after.contains_key(*path))
+        .cloned();
+    let mut changes: Vec<PathBuf> = modified.chain(removed).collect();
+    changes.sort();
+    changes
+}
+
+/// Reads the answers out of the TSV output of a day binary.
+pub fn parse_answers(tsv: &str) -> Answers {
+    tsv.lines()
+        .filter(|line| !line.starts_with("year\t"))
+        .filter_map(|line| {
+            let mut fields = line.split('\t').skip(2);
+            let part = fields.next()?.parse().ok()?;
+            let answer = fields.next()?;
+            Some((part, answer.to_string()))
+        })
+        .collect()
+}
+
+/// One line per part, comparing the answers of this run with the ones of the previous run.
+pub fn diff(previous: &Answers, current: &Answers) -> Vec<String> {
+    let parts: std::collections::BTreeSet<&u8> = previous.keys().chain(current.keys()).collect();
+    parts
+        .into_iter()
+        .map(|part| match (previous.get(part), current.get(part)) {
+            (None, Some(answer)) => format!("part {}: {}", part, answer),
+            (Some(before), Some(answer)) if before == answer => {
+                format!("part {}: {} (unchanged)", part, answer)
+            }
+            (Some(before), Some(answer)) => format!("part {}: {} (was {})", part, answer, before),
+            (Some(before), None) => format!("part {}: no answer (was {})", part, before),
+            (None, None) => unreachable!("part {} comes from one of the answers", part),
+        })
+        .collect()
+}
+
+/// What is watched for `day`: its sources, its tests, its examples, its manifest and its
+/// input, and the sources and manifest of `common`, which every day is built on.
+fn watched_paths(day: &Day) -> Vec<PathBuf> {
+    let crate_dir = day.crate_dir();
+    let common_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives inside the workspace")
+        .join("common");
+    let input =
+        input::locate(day.id(), &crate_dir, None).unwrap_or_else(|_| crate_dir.join(INPUT_FILE));
+    vec![
+        crate_dir.join("src"),
+        crate_dir.join("tests"),
+        crate_dir.join(EXAMPLES_DIR),
+        crate_dir.join("Cargo.toml"),
+        input,
+        common_dir.join("src"),
+        common_dir.join("Cargo.toml"),
+    ]
+}
+
+/// Runs `cargo <subcommand>` quietly on the package of `day`, passing it `args`.
+fn cargo(day: &Day, subcommand: &str, args: &[&str]) -> io::Result<Output> {
+    let workspace = day.crate_dir().parent().map(Path::to_path_buf);
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo")));
+    if let Some(workspace) = workspace {
+        command.current_dir(workspace);
+    }
+    command
+        .args([subcommand, "-q", "-p"])
+        .arg(day.id().crate_name())
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+}
+
+/// Runs the tests and the solution of `day` once, printing how the answers compare with
+/// `previous`, which is then replaced with the new answers.
+fn run_once(day: &Day, previous: &mut Answers) {
+    match cargo(day, "test", &[]) {
+        Ok(output) if output.status.success() => println!("tests passed"),
+        Ok(output) => {
+            let _ = io::stdout().write_all(&output.stdout);
+            let _ = io::stdout().write_all(&output.stderr);
+            println!("tests FAILED");
+        }
+        Err(err) => eprintln!("error: couldn't run cargo: {}", err),
+    }
+    match cargo(day, "run", &["--", "--format", "tsv"]) {
+        Ok(output) if output.status.success() => {
+            let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+            diff(previous, &answers)
+                .iter()
+                .for_each(|line| println!("{}", line));
+            *previous = answers;
+        }
+        Ok(output) => {
+            let _ = io::stdout().write_all(&output.stderr);
+            println!("solution FAILED");
+        }
+        Err(err) => eprintln!("error: couldn't run cargo: {}", err),
+    }
+}
+
+/// Runs the tests and the solution of `day`, then again every time one of its sources or its
+/// input changes, checking every `interval`. Never returns.
+pub fn watch(day: &Day, interval: Duration) -> ! {
+    let paths = watched_paths(day);
+    println!("watching:");
+    paths
+        .iter()
+        .for_each(|path| println!("    {}", path.display()));
+
+    let mut seen = snapshot(&paths);
+    let mut previous = Answers::new();
+    run_once(day, &mut previous);
+    loop {
+        thread::sleep(interval);
+        let changed = changes(&seen, &snapshot(&paths));
+        if changed.is_empty() {
+            continue;
+        }
+        // Editors often save in several steps, give them time to finish.
+        thread::sleep(interval);
+        seen = snapshot(&paths);
+        println!("{}", "-".repeat(80));
+        changed
+            .iter()
+            .for_each(|path| println!("changed: {}", path.display()));
+        run_once(day, &mut previous);
+    }
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> common::scratch::ScratchDir {
+    common::scratch::ScratchDir::new(&format!("watch-{}", name))
+}
+
+#[test]
+fn test_snapshot_changes() {
+    let dir = scratch_dir("snapshot");
+    let src = dir.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("lib.rs"), "").unwrap();
+    fs::write(src.join("main.rs"), "").unwrap();
+    let input = dir.join("input.txt");
+    let paths = vec![src.clone(), input.clone()];
+
+    let before = snapshot(&paths);
+    assert_eq!(before.len(), 2);
+    assert!(changes(&before, &snapshot(&paths)).is_empty());
+
+    let lib = fs::File::options()
+        .write(true)
+        .open(src.join("lib.rs"))
+        .unwrap();
+    lib.set_modified(SystemTime::now() + Duration::from_secs(5))
+        .unwrap();
+    fs::remove_file(src.join("main.rs")).unwrap();
+    fs::write(&input, "1721").unwrap();
+    assert_eq!(
+        changes(&before, &snapshot(&paths)),
+        vec![input, src.join("lib.rs"), src.join("main.rs")]
+    );
+}
+
+#[test]
+fn test_watched_paths() {
+    let day = crate::days::find(common::puzzle::PuzzleId::new(2020, 4)).unwrap();
+    let paths = watched_paths(day);
+    let common_dir = day.crate_dir().parent().unwrap().join("common");
+    assert!(paths.contains(&day.crate_dir().join("src")));
+    assert!(paths.contains(&common_dir.join("src")));
+    assert!(paths.contains(&common_dir.join("Cargo.toml")));
+    assert!(snapshot(&paths).contains_key(&common_dir.join("src").join("lib.rs")));
+}
+
+#[test]
+fn test_parse_answers() {
+    let tsv = "year\tday\tpart\tanswer\tparse_ns\tsolve_ns\tinput\n2020\t4\t1\t235\t10\t20\tin.txt\n2020\t4\t2\t194\t10\t20\tin.txt\n";
+    let answers = parse_answers(tsv);
+    assert_eq!(answers.get(&1).map(String::as_str), Some("235"));
+    assert_eq!(answers.get(&2).map(String::as_str), Some("194"));
+    assert_eq!(answers.len(), 2);
+}
+
+#[test]
+fn test_diff() {
+    let answers = |pairs: &[(u8, &str)]| -> Answers {
+        pairs.iter().map(|&(p, a)| (p, a.to_string())).collect()
+    };
+    assert_eq!(
+        diff(&Answers::new(), &answers(&[(1, "235"), (2, "194")])),
+        vec!["part 1: 235", "part 2: 194"]
+    );
+    assert_eq!(
+        diff(
+            &answers(&[(1, "235"), (2, "194")]),
+            &answers(&[(1, "235"), (2, "195")])
+        ),
+        vec!["part 1: 235 (unchanged)", "part 2: 195 (was 194)"]
+    );
+    assert_eq!(
+        diff(&answers(&[(1, "235"), (2, "194")]), &answers(&[(1, "235")])),
+        vec!["part 1: 235 (unchanged)", "part 2: no answer (was 194)"]
+    );
+}
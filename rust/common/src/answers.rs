@@ -0,0 +1,141 @@
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The file, inside each day's crate, where accepted answers are kept.
+pub const ANSWERS_FILE: &str = "answers";
+
+/// The accepted answers of a day, stored as one `partN: answer` line per part. Parts without
+/// an accepted answer yet are left out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KnownAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// How a freshly computed answer compares to the accepted one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Match,
+    Changed {
+        expected: String,
+    },
+    /// There is no accepted answer to compare with.
+    Unknown,
+}
+
+impl KnownAnswers {
+    pub fn path(crate_dir: &Path) -> PathBuf {
+        crate_dir.join(ANSWERS_FILE)
+    }
+
+    /// Loads the answers of the crate at `crate_dir`. A missing file means no answer was
+    /// accepted yet.
+    pub fn load(crate_dir: &Path) -> io::Result<Self> {
+        let path = Self::path(crate_dir);
+        match read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, crate_dir: &Path) -> io::Result<()> {
+        write(Self::path(crate_dir), self.to_string())
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (part, answer) = match line.split_once(':') {
+                Some((key, answer)) => match key.trim() {
+                    "part1" => (1, answer.trim()),
+                    "part2" => (2, answer.trim()),
+                    key => return Err(format!("line {}: unknown key {:?}", number + 1, key)),
+                },
+                None => return Err(format!("line {}: expected `partN: answer`", number + 1)),
+            };
+            answers.set(part, answer.to_string());
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part1 = Some(answer),
+            2 => self.part2 = Some(answer),
+            _ => panic!("there is no part {}", part),
+        }
+    }
+
+    pub fn check(&self, part: u8, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(expected) if expected == answer => Verdict::Match,
+            Some(expected) => Verdict::Changed {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for KnownAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in 1..=2 {
+            if let Some(answer) = self.get(part) {
+                writeln!(f, "part{}: {}", part, answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse_and_format() {
+    let answers = KnownAnswers::parse("# day 5\npart1: 978\n\npart2:727\n").unwrap();
+    assert_eq!(answers.get(1), Some("978"));
+    assert_eq!(answers.get(2), Some("727"));
+    assert_eq!(answers.to_string(), "part1: 978\npart2: 727\n");
+
+    let partial = KnownAnswers::parse("part2: abc").unwrap();
+    assert_eq!(partial.get(1), None);
+    assert_eq!(partial.to_string(), "part2: abc\n");
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(KnownAnswers::parse("part3: 1").is_err());
+    assert!(KnownAnswers::parse("part1 1").is_err());
+}
+
+#[test]
+fn test_check() {
+    let mut answers = KnownAnswers::default();
+    answers.set(1, "42".to_string());
+    assert_eq!(answers.check(1, "42"), Verdict::Match);
+    assert_eq!(
+        answers.check(1, "43"),
+        Verdict::Changed {
+            expected: "42".to_string()
+        }
+    );
+    assert_eq!(answers.check(2, "1"), Verdict::Unknown);
+}
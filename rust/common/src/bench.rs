@@ -0,0 +1,212 @@
+use crate::error::ParseError;
+use crate::Solution;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How many times a benchmarked function runs before (`warmup`) and while (`samples`) it is
+/// measured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            samples: 20,
+        }
+    }
+}
+
+/// Summary statistics of a set of timing samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "can't compute stats without samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            samples: n,
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+            min: sorted[0],
+            max: sorted[n - 1],
+        }
+    }
+
+    /// Half-width of the 95% confidence interval of the mean (normal approximation).
+    pub fn ci95(&self) -> Duration {
+        self.stddev.mul_f64(1.96 / (self.samples as f64).sqrt())
+    }
+}
+
+/// The stats of a single named benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchResult {
+    pub name: String,
+    pub stats: Stats,
+}
+
+/// Runs `f` `config.warmup` times without measuring it, then `config.samples` times measuring
+/// each call separately.
+pub fn measure<T>(config: &BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+    let samples: Vec<Duration> = (0..config.samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+/// Benchmarks the phases of a solution apart from each other: `parse` on the raw input, then
+/// `part1` and `part2` on an input that was parsed once up front.
+pub fn bench_solution<S: Solution>(
+    input: &str,
+    config: &BenchConfig,
+) -> Result<Vec<BenchResult>, ParseError> {
+    let parsed = S::parse(input)?;
+    let parse = measure(config, || S::parse(black_box(input)));
+    let part1 = measure(config, || S::part1(black_box(&parsed)));
+    let part2 = measure(config, || S::part2(black_box(&parsed)));
+    Ok(vec![
+        BenchResult {
+            name: "parse".to_string(),
+            stats: parse,
+        },
+        BenchResult {
+            name: "part1".to_string(),
+            stats: part1,
+        },
+        BenchResult {
+            name: "part2".to_string(),
+            stats: part2,
+        },
+    ])
+}
+
+/// Formats the results as an aligned, human-readable table.
+pub fn table(results: &[BenchResult]) -> String {
+    let name_width = results
+        .iter()
+        .map(|result| result.name.len())
+        .chain(std::iter::once("name".len()))
+        .max()
+        .unwrap_or(0);
+    let mut table = format!(
+        "{:<name_width$}  {:>7}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+        "name",
+        "samples",
+        "median",
+        "mean",
+        "± 95%",
+        "stddev",
+        "min",
+        "max",
+        name_width = name_width,
+    );
+    for result in results {
+        let stats = &result.stats;
+        table.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+            result.name,
+            stats.samples,
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.ci95()),
+            format!("{:.2?}", stats.stddev),
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.max),
+            name_width = name_width,
+        ));
+    }
+    table
+}
+
+/// Formats the results as tab-separated values, with every duration in nanoseconds.
+pub fn tsv(results: &[BenchResult]) -> String {
+    let mut tsv =
+        "name\tsamples\tmedian_ns\tmean_ns\tci95_ns\tstddev_ns\tmin_ns\tmax_ns\n".to_string();
+    for result in results {
+        let stats = &result.stats;
+        tsv.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            result.name,
+            stats.samples,
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.ci95().as_nanos(),
+            stats.stddev.as_nanos(),
+            stats.min.as_nanos(),
+            stats.max.as_nanos(),
+        ));
+    }
+    tsv
+}
+
+#[test]
+fn test_stats() {
+    let samples: Vec<Duration> = [4, 2, 8, 6]
+        .iter()
+        .map(|&ms| Duration::from_millis(ms))
+        .collect();
+    let stats = Stats::from_samples(&samples);
+    assert_eq!(stats.samples, 4);
+    assert_eq!(stats.median, Duration::from_millis(5));
+    assert_eq!(stats.mean, Duration::from_millis(5));
+    assert_eq!(stats.min, Duration::from_millis(2));
+    assert_eq!(stats.max, Duration::from_millis(8));
+    // sqrt((1 + 9 + 9 + 1) / 3) ms ≈ 2.582 ms
+    assert_eq!(stats.stddev.as_micros(), 2581);
+}
+
+#[test]
+fn test_stats_single_sample() {
+    let stats = Stats::from_samples(&[Duration::from_millis(3)]);
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.stddev, Duration::from_millis(0));
+    assert_eq!(stats.ci95(), Duration::from_millis(0));
+}
+
+#[test]
+fn test_measure_runs_warmup_and_samples() {
+    let mut calls = 0;
+    let stats = measure(
+        &BenchConfig {
+            warmup: 2,
+            samples: 5,
+        },
+        || calls += 1,
+    );
+    assert_eq!(calls, 7);
+    assert_eq!(stats.samples, 5);
+}
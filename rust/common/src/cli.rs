@@ -0,0 +1,166 @@
+use crate::error::ParseError;
+use crate::input::{self, Source};
+use crate::output::{Format, Record};
+use crate::puzzle::PuzzleId;
+use crate::trace::{self, Trace};
+use crate::Solution;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// The arguments every day binary accepts: `[--format human|json|tsv] [--trace FILE]
+/// [INPUT...]`, where an input is a file path or `-` for stdin.
+#[derive(Debug, PartialEq)]
+pub struct DayArgs {
+    pub format: Format,
+    pub sources: Vec<Source>,
+    /// Where to write a [`trace`] of the run, if anywhere.
+    pub trace: Option<PathBuf>,
+}
+
+impl DayArgs {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut format = Format::Human;
+        let mut sources = Vec::new();
+        let mut trace = None;
+        let mut args = args.iter().map(String::as_str);
+        while let Some(arg) = args.next() {
+            match arg {
+                "--format" | "-f" => format = args.next().ok_or("missing format")?.parse()?,
+                "--trace" => trace = Some(PathBuf::from(args.next().ok_or("missing trace file")?)),
+                arg => sources.push(Source::from(arg)),
+            }
+        }
+        Ok(DayArgs {
+            format,
+            sources,
+            trace,
+        })
+    }
+}
+
+/// The entry point of the day binaries. Reads every input named on the command line, or the
+/// default input when there are none, and solves them one after the other.
+///
+/// With the human format each input gets a header with its label and is handed to `human`,
+/// which prints the day's own prose. Other formats print one record per part instead. Inputs
+/// that can't be read or parsed are reported and make the process exit with an error once the
+/// others are solved.
+///
+/// With `--trace FILE`, every input is a span named after the puzzle, around its `read` span
+/// and the `parse`, `part1` and `part2` spans of [`solve`](crate::solve). With the human format
+/// `human` opens those spans itself. The trace is written to the file and printed as a tree on
+/// stderr.
+pub fn day_main<S: Solution>(
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    mut human: impl FnMut(&str) -> Result<(), ParseError>,
+) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = DayArgs::parse(&args).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        eprintln!(
+            "usage: {} [--format human|json|tsv] [--trace FILE] [INPUT...]",
+            puzzle.crate_name()
+        );
+        process::exit(2);
+    });
+    let reads: Vec<Option<&Source>> = if args.sources.is_empty() {
+        vec![None]
+    } else {
+        args.sources.iter().map(Some).collect()
+    };
+
+    if let Some(header) = args.format.header() {
+        println!("{}", header);
+    }
+    if args.trace.is_some() {
+        trace::start();
+    }
+    let mut failed = false;
+    for source in reads {
+        let _span = trace::span(&puzzle.to_string());
+        let input = match input::read(puzzle, crate_dir, source) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed = true;
+                continue;
+            }
+        };
+        let solved = if args.format == Format::Human {
+            println!("==> {} <==", input.label);
+            human(&input.contents)
+        } else {
+            crate::solve::<S>(&input.contents, &[1, 2]).map(|solved| {
+                solved.into_iter().for_each(|solved| {
+                    let record = Record::new(puzzle, &input.label, solved);
+                    println!("{}", args.format.record(&record))
+                })
+            })
+        };
+        if let Err(err) = solved {
+            eprintln!("error: {}: {}", input.label, err);
+            failed = true;
+        }
+    }
+    if let (Some(path), Some(trace)) = (&args.trace, trace::finish()) {
+        failed |= !write_trace(path, &trace);
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Writes `trace` to `path` for the trace viewers and prints it as a tree on stderr. Returns
+/// whether it could be written.
+pub fn write_trace(path: &Path, trace: &Trace) -> bool {
+    eprint!("{}", trace.tree());
+    match fs::write(path, trace.chrome_json()) {
+        Ok(()) => {
+            eprintln!("trace written to {}", path.display());
+            true
+        }
+        Err(err) => {
+            eprintln!(
+                "error: couldn't write the trace to {}: {}",
+                path.display(),
+                err
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+fn args(input: &str) -> Vec<String> {
+    input.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_parse_day_args() {
+    assert_eq!(
+        DayArgs::parse(&args("a.txt --format tsv -")),
+        Ok(DayArgs {
+            format: Format::Tsv,
+            sources: vec![Source::from("a.txt"), Source::Stdin],
+            trace: None,
+        })
+    );
+    assert_eq!(
+        DayArgs::parse(&args("")),
+        Ok(DayArgs {
+            format: Format::Human,
+            sources: vec![],
+            trace: None,
+        })
+    );
+    assert_eq!(
+        DayArgs::parse(&args("--trace day4.trace.json")).map(|args| args.trace),
+        Ok(Some(PathBuf::from("day4.trace.json")))
+    );
+    assert!(DayArgs::parse(&args("--trace")).is_err());
+    assert!(DayArgs::parse(&args("--format")).is_err());
+    assert!(DayArgs::parse(&args("-f yaml")).is_err());
+}
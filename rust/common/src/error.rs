@@ -0,0 +1,171 @@
+use crate::trace;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A position in the input. Both are 1-based and `column` counts characters, not bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Location { line, column }
+    }
+
+    /// The location of `part`, which must be a slice of `line`, on line number `number`.
+    pub fn of(number: usize, line: &str, part: &str) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset <= line.len())
+            .expect("part must be a slice of line");
+        Location::new(number, line[..offset].chars().count() + 1)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Why a puzzle input couldn't be parsed, and where.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// `text` should have been a number.
+    InvalidNumber { at: Location, text: String },
+    /// `text` isn't what was `expected` there.
+    Unexpected {
+        at: Location,
+        text: String,
+        expected: &'static str,
+    },
+    /// The input ended, or the line did, before `expected` was found.
+    Missing {
+        at: Location,
+        expected: &'static str,
+    },
+}
+
+impl ParseError {
+    pub fn location(&self) -> Location {
+        match self {
+            ParseError::InvalidNumber { at, .. }
+            | ParseError::Unexpected { at, .. }
+            | ParseError::Missing { at, .. } => *at,
+        }
+    }
+
+    /// Moves the error `lines` lines down. Parsers of a single line or record report line 1,
+    /// this turns it into the line of the whole input the record started on.
+    pub fn below(mut self, lines: usize) -> Self {
+        match &mut self {
+            ParseError::InvalidNumber { at, .. }
+            | ParseError::Unexpected { at, .. }
+            | ParseError::Missing { at, .. } => at.line += lines,
+        }
+        self
+    }
+
+    /// Moves the error `columns` characters right. Parsers handed a part of a line report
+    /// columns in that part, this turns them into columns of the whole line.
+    pub fn right(mut self, columns: usize) -> Self {
+        match &mut self {
+            ParseError::InvalidNumber { at, .. }
+            | ParseError::Unexpected { at, .. }
+            | ParseError::Missing { at, .. } => at.column += columns,
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber { at, text } => {
+                write!(f, "{}: invalid number {:?}", at, text)
+            }
+            ParseError::Unexpected { at, text, expected } => {
+                write!(f, "{}: expected {}, found {:?}", at, expected, text)
+            }
+            ParseError::Missing { at, expected } => write!(f, "{}: missing {}", at, expected),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, found at `at`, as a number.
+pub fn parse_number<T: FromStr>(text: &str, at: Location) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::InvalidNumber {
+        at,
+        text: text.to_string(),
+    })
+}
+
+/// Parses every line of `input` with `T`'s [`FromStr`], fixing up the line of any error. Each
+/// line counts as one of the `records` of the current [`trace`](crate::trace) span.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    let parsed: Vec<T> = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| line.parse().map_err(|err: ParseError| err.below(index)))
+        .collect::<Result<_, _>>()?;
+    trace::count("records", parsed.len() as u64);
+    Ok(parsed)
+}
+
+#[test]
+fn test_location_of() {
+    let line = "1-3 ä: abcde";
+    assert_eq!(Location::of(4, line, &line[8..]), Location::new(4, 8));
+    assert_eq!(Location::of(4, line, line), Location::new(4, 1));
+    assert_eq!(
+        Location::of(4, line, &line[line.len()..]),
+        Location::new(4, 13)
+    );
+}
+
+#[test]
+fn test_display() {
+    let err = ParseError::Unexpected {
+        at: Location::new(1, 8),
+        text: "x".to_string(),
+        expected: "`L` or `R`",
+    };
+    assert_eq!(
+        err.to_string(),
+        "line 1, column 8: expected `L` or `R`, found \"x\""
+    );
+    assert_eq!(err.below(9).location(), Location::new(10, 8));
+}
+
+#[test]
+fn test_parse_lines() {
+    assert_eq!(parse_lines::<Digit>("1\n2"), Ok(vec![Digit(1), Digit(2)]));
+    assert_eq!(
+        parse_lines::<Digit>("1\n2\nx"),
+        Err(ParseError::InvalidNumber {
+            at: Location::new(3, 1),
+            text: "x".to_string(),
+        })
+    );
+}
+
+#[cfg(test)]
+#[derive(Debug, PartialEq)]
+struct Digit(u8);
+
+#[cfg(test)]
+impl FromStr for Digit {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        parse_number(line, Location::new(1, 1)).map(Digit)
+    }
+}
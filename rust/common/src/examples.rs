@@ -0,0 +1,184 @@
+use crate::answers::KnownAnswers;
+use crate::{solve, Solution};
+use std::fs::{read_dir, read_to_string};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The directory, inside each day's crate, where examples are kept. Each example is an input
+/// file `NAME.txt` next to a sidecar `NAME.answers` in the format of the answers file, listing
+/// the answer of each part the example covers.
+pub const EXAMPLES_DIR: &str = "examples";
+
+const INPUT_EXTENSION: &str = "txt";
+const ANSWERS_EXTENSION: &str = "answers";
+
+/// An example input with the answers it should give.
+#[derive(Debug)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: KnownAnswers,
+}
+
+fn invalid_data(path: &Path, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), message),
+    )
+}
+
+/// Loads every example of the crate at `crate_dir`, sorted by name. A crate without an examples
+/// directory has no examples. An input without its sidecar, or the other way around, is an
+/// error rather than an example that silently checks nothing.
+pub fn discover(crate_dir: &Path) -> io::Result<Vec<Example>> {
+    let dir = crate_dir.join(EXAMPLES_DIR);
+    let entries = match read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    paths.sort();
+
+    let mut examples = Vec::new();
+    for path in &paths {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if extension == Some(ANSWERS_EXTENSION) {
+            if !path.with_extension(INPUT_EXTENSION).is_file() {
+                return Err(invalid_data(
+                    path,
+                    "answers without an example input next to it",
+                ));
+            }
+            continue;
+        }
+        if extension != Some(INPUT_EXTENSION) {
+            continue;
+        }
+        let sidecar = path.with_extension(ANSWERS_EXTENSION);
+        let answers = match read_to_string(&sidecar) {
+            Ok(contents) => {
+                KnownAnswers::parse(&contents).map_err(|err| invalid_data(&sidecar, err))?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(invalid_data(path, "example without answers next to it"))
+            }
+            Err(err) => return Err(err),
+        };
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        examples.push(Example {
+            name,
+            input: read_to_string(path)?,
+            answers,
+        });
+    }
+    Ok(examples)
+}
+
+/// Solves an example with `S` and describes every part that doesn't give the expected answer.
+pub fn check<S: Solution>(example: &Example) -> Vec<String> {
+    let parts: Vec<u8> = (1..=2)
+        .filter(|&part| example.answers.get(part).is_some())
+        .collect();
+    if parts.is_empty() {
+        return vec![format!("{}: no answer to check", example.name)];
+    }
+    match solve::<S>(&example.input, &parts) {
+        Ok(solved) => solved
+            .into_iter()
+            .filter_map(|solved| {
+                let expected = example.answers.get(solved.part)?;
+                if solved.answer == expected {
+                    None
+                } else {
+                    Some(format!(
+                        "{} part {}: expected {}, got {}",
+                        example.name, solved.part, expected, solved.answer
+                    ))
+                }
+            })
+            .collect(),
+        Err(err) => vec![format!("{}: {}", example.name, err)],
+    }
+}
+
+/// Checks every example of the crate at `crate_dir` with `S`, panicking with all the failures
+/// at once. Meant to be called from a test of each day.
+pub fn assert_examples<S: Solution>(crate_dir: &Path) {
+    let examples = discover(crate_dir).unwrap_or_else(|err| panic!("{}", err));
+    let failures: Vec<String> = examples.iter().flat_map(check::<S>).collect();
+    assert!(
+        failures.is_empty(),
+        "{} of {} examples failed:\n{}",
+        failures.len(),
+        examples.len(),
+        failures.join("\n")
+    );
+}
+
+#[cfg(test)]
+fn scratch_crate(name: &str, files: &[(&str, &str)]) -> crate::scratch::ScratchDir {
+    let dir = crate::scratch::ScratchDir::new(&format!("examples-{}", name));
+    std::fs::create_dir_all(dir.join(EXAMPLES_DIR)).unwrap();
+    for (file, contents) in files {
+        std::fs::write(dir.join(EXAMPLES_DIR).join(file), contents).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_discover() {
+    let dir = scratch_crate(
+        "discover",
+        &[
+            ("short.txt", "a\nb"),
+            ("short.answers", "part1: 2\n"),
+            ("long.txt", "a\nb\nc"),
+            ("long.answers", "part1: 3\npart2: 3\n"),
+            ("README.md", "ignored"),
+        ],
+    );
+    let examples = discover(&dir).unwrap();
+    let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["long", "short"]);
+    assert_eq!(examples[1].input, "a\nb");
+    assert_eq!(examples[1].answers.get(2), None);
+
+    assert!(discover(&dir.join("nothing")).unwrap().is_empty());
+}
+
+#[test]
+fn test_discover_orphans() {
+    let dir = scratch_crate("no-answers", &[("example.txt", "a")]);
+    assert!(discover(&dir).is_err());
+    let dir = scratch_crate("no-input", &[("example.answers", "part1: 1")]);
+    assert!(discover(&dir).is_err());
+    let dir = scratch_crate(
+        "bad-answers",
+        &[("example.txt", "a"), ("example.answers", "part3: 1")],
+    );
+    assert!(discover(&dir).is_err());
+}
+
+#[test]
+fn test_check() {
+    let example = |input: &str, answers: &str| Example {
+        name: "example".to_string(),
+        input: input.to_string(),
+        answers: KnownAnswers::parse(answers).unwrap(),
+    };
+    assert!(check::<crate::LineCount>(&example("a\nb", "part1: 2\npart2: 2")).is_empty());
+    assert_eq!(
+        check::<crate::LineCount>(&example("a\nb", "part2: 3")),
+        vec!["example part 2: expected 3, got 2"]
+    );
+    assert_eq!(
+        check::<crate::LineCount>(&example("a\nb", "")),
+        vec!["example: no answer to check"]
+    );
+}
@@ -0,0 +1,364 @@
+use crate::examples;
+use crate::random::Rng;
+use std::any::Any;
+use std::cell::Cell;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::Once;
+
+/// Overrides the seed of [`FuzzConfig::from_env`]. `random` picks a new one on every run.
+pub const SEED_ENV: &str = "AOC_FUZZ_SEED";
+/// Overrides the number of cases of [`FuzzConfig::from_env`].
+pub const CASES_ENV: &str = "AOC_FUZZ_CASES";
+
+/// Characters that tend to break parsers, mixed into the mutated inputs next to the ones of the
+/// valid inputs: separators, signs, digits, whitespace and a few multi-byte characters to catch
+/// slicing in the middle of one.
+const INTERESTING: &[char] = &[
+    ' ', '\t', '\n', '\r', ':', '-', '#', '+', '0', '1', '9', 'a', 'z', 'é', '€', '💥', '\0',
+];
+
+/// Tokens worth splicing in whole: numbers at the edges of the usual integer types, and so on.
+const TOKENS: &[&str] = &[
+    "0",
+    "-1",
+    "255",
+    "256",
+    "2147483648",
+    "9223372036854775808",
+    "99999999999999999999999",
+    "\n\n",
+    "\r\n",
+    ": :",
+    "--",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FuzzConfig {
+    pub seed: u64,
+    pub cases: usize,
+}
+
+impl Default for FuzzConfig {
+    /// A fixed seed, so `cargo test` always checks the same inputs.
+    fn default() -> Self {
+        FuzzConfig {
+            seed: 2020,
+            cases: 2000,
+        }
+    }
+}
+
+impl FuzzConfig {
+    /// The default config, with the seed and number of cases taken from `$AOC_FUZZ_SEED` and
+    /// `$AOC_FUZZ_CASES` when they are set.
+    ///
+    /// Panics when one of them isn't valid, rather than silently running something else.
+    pub fn from_env() -> Self {
+        let mut config = FuzzConfig::default();
+        if let Ok(seed) = env::var(SEED_ENV) {
+            config.seed = match seed.as_str() {
+                "random" => Rng::from_time().next_u64(),
+                seed => seed
+                    .parse()
+                    .unwrap_or_else(|_| panic!("${} isn't a number: {:?}", SEED_ENV, seed)),
+            };
+        }
+        if let Ok(cases) = env::var(CASES_ENV) {
+            config.cases = cases
+                .parse()
+                .unwrap_or_else(|_| panic!("${} isn't a number: {:?}", CASES_ENV, cases));
+        }
+        config
+    }
+}
+
+/// An input that made the target panic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub name: String,
+    pub seed: u64,
+    /// The case, counted from 0, that found the panic.
+    pub case: usize,
+    pub input: String,
+    /// The smallest input found that still panics, with the same message.
+    pub minimal: String,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} panicked on case {} of seed {}: {}\n  minimal input: {:?}\n  original input: {:?}\n  \
+             run again with {}={}",
+            self.name, self.case, self.seed, self.message, self.minimal, self.input, SEED_ENV, self.seed
+        )
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, turning a panic into its message. The panic isn't printed: a failure is reported
+/// once with its minimal input, not for every attempt made to find it. Panics of other threads
+/// are still printed.
+pub fn catch_quietly<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info)
+            }
+        }));
+    });
+    QUIET.with(|quiet| quiet.set(true));
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload));
+    QUIET.with(|quiet| quiet.set(false));
+    result
+}
+
+fn run(target: &dyn Fn(&str), input: &str) -> Result<(), String> {
+    catch_quietly(|| target(input))
+}
+
+/// Valid inputs to start from: every example of the crate at `crate_dir`, then each of their
+/// lines on its own, for the parsers of a single line.
+///
+/// Panics when the examples can't be loaded.
+pub fn example_corpus(crate_dir: &Path) -> Vec<String> {
+    let examples = examples::discover(crate_dir).unwrap_or_else(|err| panic!("{}", err));
+    let mut corpus: Vec<String> = examples.iter().map(|e| e.input.clone()).collect();
+    corpus.extend(
+        examples
+            .iter()
+            .flat_map(|e| e.input.lines())
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from),
+    );
+    corpus
+}
+
+/// A new input: most of the time a mutation of one of the `corpus` inputs, sometimes garbage
+/// made of the interesting characters and the ones of the corpus.
+pub fn generate<S: AsRef<str>>(rng: &mut Rng, corpus: &[S], alphabet: &[char]) -> String {
+    if corpus.is_empty() || rng.chance(1, 8) {
+        let length = rng.below(64);
+        return (0..length).map(|_| *rng.pick(alphabet)).collect();
+    }
+    let mut chars: Vec<char> = rng.pick(corpus).as_ref().chars().collect();
+    for _ in 0..=rng.below(4) {
+        mutate(rng, &mut chars, corpus, alphabet);
+    }
+    chars.into_iter().collect()
+}
+
+fn mutate<S: AsRef<str>>(rng: &mut Rng, chars: &mut Vec<char>, corpus: &[S], alphabet: &[char]) {
+    let at = |rng: &mut Rng, chars: &Vec<char>| rng.below(chars.len() + 1);
+    match rng.below(8) {
+        // Replace a character.
+        0 if !chars.is_empty() => {
+            let index = rng.below(chars.len());
+            chars[index] = *rng.pick(alphabet);
+        }
+        // Insert a few characters.
+        1 => {
+            let index = at(rng, chars);
+            let inserted: Vec<char> = (0..=rng.below(3)).map(|_| *rng.pick(alphabet)).collect();
+            chars.splice(index..index, inserted);
+        }
+        // Remove a range.
+        2 if !chars.is_empty() => {
+            let start = rng.below(chars.len());
+            let end = start + rng.below(chars.len() - start) + 1;
+            chars.drain(start..end);
+        }
+        // Repeat a range.
+        3 if !chars.is_empty() => {
+            let start = rng.below(chars.len());
+            let end = start + rng.below((chars.len() - start).min(16)) + 1;
+            let copy: Vec<char> = chars[start..end].to_vec();
+            chars.splice(end..end, copy);
+        }
+        // Cut the end off.
+        4 => {
+            let length = at(rng, chars);
+            chars.truncate(length);
+        }
+        // Splice in a token.
+        5 => {
+            let index = at(rng, chars);
+            chars.splice(index..index, rng.pick(TOKENS).chars());
+        }
+        // Splice in a piece of another input.
+        6 => {
+            let other: Vec<char> = rng.pick(corpus).as_ref().chars().collect();
+            if !other.is_empty() {
+                let start = rng.below(other.len());
+                let end = start + rng.below(other.len() - start) + 1;
+                let index = at(rng, chars);
+                chars.splice(index..index, other[start..end].iter().copied());
+            }
+        }
+        // Swap two characters.
+        _ if chars.len() > 1 => {
+            let (a, b) = (rng.below(chars.len()), rng.below(chars.len()));
+            chars.swap(a, b);
+        }
+        _ => chars.push(*rng.pick(alphabet)),
+    }
+}
+
+/// Shrinks `input` while `target` keeps panicking with `message`: removes chunks of characters,
+/// from half of the input down to single characters, for as long as one of them can go.
+pub fn minimize(target: &dyn Fn(&str), input: &str, message: &str) -> String {
+    let still_fails = |chars: &[char]| {
+        let candidate: String = chars.iter().collect();
+        run(target, &candidate).err().as_deref() == Some(message)
+    };
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut chunk = (chars.len() / 2).max(1);
+    loop {
+        let mut removed_any = false;
+        let mut start = 0;
+        while start < chars.len() {
+            let end = (start + chunk).min(chars.len());
+            let candidate: Vec<char> = chars[..start]
+                .iter()
+                .chain(&chars[end..])
+                .copied()
+                .collect();
+            if still_fails(&candidate) {
+                chars = candidate;
+                removed_any = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if chunk == 1 && !removed_any {
+            break;
+        }
+        if !removed_any {
+            chunk = (chunk / 2).max(1);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Feeds `config.cases` inputs derived from `corpus` to `target`, and returns the first one that
+/// panics, shrunk to a minimal input. The corpus inputs themselves are tried first.
+pub fn fuzz<S: AsRef<str>>(
+    name: &str,
+    config: &FuzzConfig,
+    corpus: &[S],
+    target: impl Fn(&str),
+) -> Option<Failure> {
+    let mut alphabet: Vec<char> = corpus
+        .iter()
+        .flat_map(|input| input.as_ref().chars())
+        .collect();
+    alphabet.extend_from_slice(INTERESTING);
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    let mut rng = Rng::new(config.seed);
+    let cases = corpus
+        .iter()
+        .map(|input| input.as_ref().to_string())
+        .chain((0..config.cases).map(|_| generate(&mut rng, corpus, &alphabet)));
+    let mut failure = None;
+    for (case, input) in cases.enumerate() {
+        if let Err(message) = run(&target, &input) {
+            failure = Some(Failure {
+                name: name.to_string(),
+                seed: config.seed,
+                case,
+                minimal: minimize(&target, &input, &message),
+                input,
+                message,
+            });
+            break;
+        }
+    }
+    failure
+}
+
+/// Same as [`fuzz`], panicking with the report of the failure. Meant to be called from a test.
+pub fn assert_no_panics<S: AsRef<str>>(
+    name: &str,
+    config: &FuzzConfig,
+    corpus: &[S],
+    target: impl Fn(&str),
+) {
+    if let Some(failure) = fuzz(name, config, corpus, target) {
+        panic!("{}", failure);
+    }
+}
+
+#[test]
+fn test_fuzz_finds_and_minimizes_a_panic() {
+    let config = FuzzConfig {
+        seed: 1,
+        cases: 5000,
+    };
+    // Panics on any line with more than one `-`, like a parser that indexes blindly would.
+    let target = |input: &str| {
+        for line in input.lines() {
+            if line.matches('-').count() > 1 {
+                panic!("two dashes");
+            }
+        }
+    };
+    let failure = fuzz("dashes", &config, &["1-3 a: abcde\n2-9 c: ccc"], target).unwrap();
+    assert_eq!(failure.message, "two dashes");
+    assert_eq!(failure.minimal, "--");
+    assert!(failure
+        .to_string()
+        .contains("run again with AOC_FUZZ_SEED=1"));
+
+    let again = fuzz("dashes", &config, &["1-3 a: abcde\n2-9 c: ccc"], target).unwrap();
+    assert_eq!(again, failure, "the same seed finds the same failure");
+}
+
+#[test]
+fn test_fuzz_without_panics() {
+    let config = FuzzConfig {
+        seed: 3,
+        cases: 500,
+    };
+    let lengths = std::cell::Cell::new(0);
+    let failure = fuzz("lengths", &config, &["FBFBBFFRLR"], |input| {
+        lengths.set(lengths.get() + input.len())
+    });
+    assert_eq!(failure, None);
+    assert!(lengths.get() > 0);
+}
+
+#[test]
+fn test_generate_is_deterministic() {
+    let corpus = ["abc\ndef"];
+    let alphabet = ['a', 'x'];
+    let inputs = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..50)
+            .map(|_| generate(&mut rng, &corpus, &alphabet))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(inputs(5), inputs(5));
+    assert_ne!(inputs(5), inputs(6));
+}
@@ -0,0 +1,345 @@
+use crate::error::{Location, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// The offsets to the 4 orthogonal neighbours of a tile: up, right, down and left.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets to all 8 neighbours of a tile, clockwise from the top left one.
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular map of tiles, addressed by `(x, y)` with `(0, 0)` at the top left corner, `x`
+/// growing to the right and `y` downwards.
+///
+/// Indexing with `grid[(x, y)]` panics outside of the grid, [`Grid::get`] doesn't and
+/// [`Grid::get_wrapping`] wraps around both edges instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its tiles, row after row.
+    ///
+    /// Panics when there aren't exactly `width * height` tiles.
+    pub fn new(width: usize, height: usize, tiles: Vec<T>) -> Self {
+        assert_eq!(
+            tiles.len(),
+            width * height,
+            "a {}x{} grid needs {} tiles",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    /// Parses one row per line, ignoring empty lines. Only the line terminator is left out of a
+    /// row, whitespace is turned into tiles like any other character. `tile` turns a character
+    /// into a tile, or returns `None` when the character isn't one, which is reported as not
+    /// being what was `expected`. Every row must be as wide as the first one.
+    pub fn parse(
+        input: &str,
+        tile: impl Fn(char) -> Option<T>,
+        expected: &'static str,
+    ) -> Result<Self, ParseError> {
+        Self::from_rows(input.lines(), tile, expected)
+    }
+
+    /// Same as [`Grid::parse`], with the lines already split. Errors are reported on the
+    /// position of the line in `lines`.
+    pub fn from_rows<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+        tile: impl Fn(char) -> Option<T>,
+        expected: &'static str,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut tiles = Vec::new();
+        for (number, line) in lines.into_iter().enumerate() {
+            let row = line.strip_suffix('\n').unwrap_or(line);
+            let row = row.strip_suffix('\r').unwrap_or(row);
+            if row.is_empty() {
+                continue;
+            }
+            for (index, c) in row.char_indices() {
+                match tile(c) {
+                    Some(tile) => tiles.push(tile),
+                    None => {
+                        return Err(ParseError::Unexpected {
+                            at: Location::of(number + 1, line, &row[index..]),
+                            text: c.to_string(),
+                            expected,
+                        })
+                    }
+                }
+            }
+            let row_width = row.chars().count();
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(ParseError::Unexpected {
+                    at: Location::of(number + 1, line, row),
+                    text: row.to_string(),
+                    expected: "a row as wide as the first one",
+                });
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, tiles))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The tile at `(x, y)`, or `None` outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self.tiles[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.tiles[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The tile at `(x, y)` on a grid that repeats itself in every direction, so `x == width`
+    /// is the first column again.
+    ///
+    /// Panics on an empty grid.
+    pub fn get_wrapping(&self, x: usize, y: usize) -> &T {
+        &self[(x % self.width, y % self.height)]
+    }
+
+    pub fn get_wrapping_mut(&mut self, x: usize, y: usize) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        &mut self[(x % width, y % height)]
+    }
+
+    /// The positions right above, right of, below and left of `(x, y)` that are inside the
+    /// grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ORTHOGONAL)
+    }
+
+    /// The positions around `(x, y)`, diagonals included, that are inside the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ALL_AROUND)
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            Some((x, y)).filter(|&(x, y)| self.contains(x, y))
+        })
+    }
+
+    /// Every tile with its position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(move |(index, tile)| ((index % width, index / width), tile))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.tiles[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// The rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| &self.tiles[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The tiles of column `x` from top to bottom. Empty outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.tiles[y * self.width + x])
+    }
+
+    /// The columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", x, y, width, height))
+    }
+}
+
+/// Writes the grid back as text, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn example() -> Grid<char> {
+    Grid::parse("\nab#\ncd.\n\n", Some, "anything").unwrap()
+}
+
+#[test]
+fn test_parse_and_display() {
+    let grid = example();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.to_string(), "ab#\ncd.");
+
+    let numbers = Grid::parse("12\n34", |c| c.to_digit(10), "a digit").unwrap();
+    assert_eq!(numbers[(1, 1)], 4);
+    assert_eq!(
+        Grid::<char>::parse("", Some, "anything").unwrap().height(),
+        0
+    );
+
+    let spaces = Grid::from_rows([" a \r\n", "b  \n"], Some, "anything").unwrap();
+    assert_eq!((spaces.width(), spaces.height()), (3, 2));
+    assert_eq!(
+        (spaces[(0, 0)], spaces[(1, 0)], spaces[(2, 1)]),
+        (' ', 'a', ' ')
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let tile = |c| Some(c).filter(|&c| c == '.' || c == '#');
+    assert_eq!(
+        Grid::parse("..#\n.x.", tile, "`.` or `#`"),
+        Err(ParseError::Unexpected {
+            at: Location::new(2, 2),
+            text: "x".to_string(),
+            expected: "`.` or `#`",
+        })
+    );
+    assert_eq!(
+        Grid::parse("..#\n\n.#", tile, "`.` or `#`"),
+        Err(ParseError::Unexpected {
+            at: Location::new(3, 1),
+            text: ".#".to_string(),
+            expected: "a row as wide as the first one",
+        })
+    );
+    assert_eq!(
+        Grid::parse("..#\n .#", tile, "`.` or `#`"),
+        Err(ParseError::Unexpected {
+            at: Location::new(2, 1),
+            text: " ".to_string(),
+            expected: "`.` or `#`",
+        })
+    );
+}
+
+#[test]
+fn test_access() {
+    let mut grid = example();
+    assert_eq!(grid.get(2, 0), Some(&'#'));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(*grid.get_wrapping(4, 3), 'd');
+
+    *grid.get_wrapping_mut(5, 1) = 'X';
+    grid[(0, 0)] = 'A';
+    assert_eq!(grid.to_string(), "Ab#\ncdX");
+}
+
+#[test]
+#[should_panic(expected = "(3, 0) is outside of the 3x2 grid")]
+fn test_index_out_of_bounds() {
+    let _ = example()[(3, 0)];
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = Grid::new(3, 3, (0..9).collect());
+    let at = |positions: Vec<(usize, usize)>| -> Vec<i32> {
+        positions.into_iter().map(|(x, y)| grid[(x, y)]).collect()
+    };
+    assert_eq!(at(grid.neighbours4(1, 1).collect()), vec![1, 5, 7, 3]);
+    assert_eq!(at(grid.neighbours4(0, 0).collect()), vec![1, 3]);
+    assert_eq!(
+        at(grid.neighbours8(1, 1).collect()),
+        vec![0, 1, 2, 5, 8, 7, 6, 3]
+    );
+    assert_eq!(at(grid.neighbours8(2, 2).collect()), vec![4, 5, 7]);
+}
+
+#[test]
+fn test_rows_and_columns() {
+    let grid = example();
+    let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+    assert_eq!(rows, vec!["ab#", "cd."]);
+    assert_eq!(grid.row(1), Some(&['c', 'd', '.'][..]));
+    assert_eq!(grid.row(2), None);
+
+    let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+    assert_eq!(columns, vec!["ac", "bd", "#."]);
+    assert_eq!(grid.column(3).count(), 0);
+
+    let trees: Vec<(usize, usize)> = grid
+        .iter()
+        .filter(|&(_, &tile)| tile == '#')
+        .map(|(position, _)| position)
+        .collect();
+    assert_eq!(trees, vec![(2, 0)]);
+}
@@ -0,0 +1,250 @@
+use crate::puzzle::PuzzleId;
+use crate::trace;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The default input of a day, inside its crate.
+pub const INPUT_FILE: &str = "input.txt";
+
+/// Environment variable pointing at a directory of inputs named `dayN.txt`, or `YEAR/dayN.txt`
+/// outside of the default year. They take precedence over the `input.txt` of each day's crate.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the candidate paths exist.
+    NotFound {
+        puzzle: PuzzleId,
+        tried: Vec<PathBuf>,
+    },
+    /// The input exists but couldn't be read.
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { puzzle, tried } => {
+                write!(f, "couldn't find the input for {}, tried:", puzzle)?;
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, source } => {
+                write!(f, "couldn't read {}: {}", path.display(), source)
+            }
+            InputError::Stdin(source) => write!(f, "couldn't read stdin: {}", source),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+            InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+/// Where an input comes from, as given on the command line: `-` is stdin, anything else is a
+/// file path.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl From<&str> for Source {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+}
+
+/// The contents of an input and the name it goes by in the output.
+#[derive(Debug, PartialEq)]
+pub struct Input {
+    pub label: String,
+    pub contents: String,
+}
+
+/// Finds the input of `puzzle`, whose crate lives at `crate_dir`, independently of the working
+/// directory. In order:
+///
+/// 1. `explicit`, when given (e.g. from a `--input` flag). Nothing else is tried then.
+/// 2. `$AOC_INPUT_DIR/dayN.txt` (`$AOC_INPUT_DIR/YEAR/dayN.txt` outside of the default year),
+///    when the variable is set.
+/// 3. `input.txt` inside the crate.
+pub fn locate(
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    explicit: Option<&Path>,
+) -> Result<PathBuf, InputError> {
+    let input_dir = env::var_os(INPUT_DIR_ENV).map(PathBuf::from);
+    locate_in(puzzle, crate_dir, explicit, input_dir.as_deref())
+}
+
+/// Reads the input of `puzzle` from `source`, or from the [`locate`]d default input when there is
+/// no source. This is the `read` span of a [`trace`], which counts its `bytes` and `lines`.
+pub fn read(
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    source: Option<&Source>,
+) -> Result<Input, InputError> {
+    let _span = trace::span("read");
+    let input = read_source(puzzle, crate_dir, source)?;
+    if trace::is_enabled() {
+        trace::count("bytes", input.contents.len() as u64);
+        trace::count("lines", input.contents.lines().count() as u64);
+    }
+    Ok(input)
+}
+
+fn read_source(
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    source: Option<&Source>,
+) -> Result<Input, InputError> {
+    let path = match source {
+        Some(Source::Stdin) => {
+            let mut contents = String::new();
+            io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(InputError::Stdin)?;
+            return Ok(Input {
+                label: "stdin".to_string(),
+                contents,
+            });
+        }
+        Some(Source::File(path)) => locate(puzzle, crate_dir, Some(path))?,
+        None => locate(puzzle, crate_dir, None)?,
+    };
+    match read_to_string(&path) {
+        Ok(contents) => Ok(Input {
+            label: path.display().to_string(),
+            contents,
+        }),
+        Err(source) => Err(InputError::Io { path, source }),
+    }
+}
+
+fn locate_in(
+    puzzle: PuzzleId,
+    crate_dir: &Path,
+    explicit: Option<&Path>,
+    input_dir: Option<&Path>,
+) -> Result<PathBuf, InputError> {
+    let candidates = match explicit {
+        Some(path) => vec![path.to_path_buf()],
+        None => input_dir
+            .map(|dir| dir.join(puzzle.input_name()))
+            .into_iter()
+            .chain(std::iter::once(crate_dir.join(INPUT_FILE)))
+            .collect(),
+    };
+    match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::NotFound {
+            puzzle,
+            tried: candidates,
+        }),
+    }
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> crate::scratch::ScratchDir {
+    crate::scratch::ScratchDir::new(&format!("input-{}", name))
+}
+
+#[test]
+fn test_locate_prefers_input_dir_over_crate() {
+    let crate_dir = scratch_dir("crate");
+    let input_dir = scratch_dir("inputs");
+    std::fs::write(crate_dir.join("input.txt"), "crate").unwrap();
+
+    let found = locate_in(PuzzleId::new(2020, 3), &crate_dir, None, Some(&input_dir)).unwrap();
+    assert_eq!(found, crate_dir.join("input.txt"));
+
+    std::fs::write(input_dir.join("day3.txt"), "override").unwrap();
+    let found = locate_in(PuzzleId::new(2020, 3), &crate_dir, None, Some(&input_dir)).unwrap();
+    assert_eq!(found, input_dir.join("day3.txt"));
+}
+
+#[test]
+fn test_locate_lists_tried_paths() {
+    let crate_dir = scratch_dir("empty-crate");
+    let input_dir = scratch_dir("empty-inputs");
+
+    let err = locate_in(PuzzleId::new(2020, 7), &crate_dir, None, Some(&input_dir)).unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("couldn't find the input for day 7, tried:"));
+    assert!(message.contains(&input_dir.join("day7.txt").display().to_string()));
+    assert!(message.contains(&crate_dir.join("input.txt").display().to_string()));
+}
+
+#[test]
+fn test_locate_explicit_path_is_not_a_fallback() {
+    let crate_dir = scratch_dir("explicit");
+    std::fs::write(crate_dir.join("input.txt"), "crate").unwrap();
+    let missing = crate_dir.join("missing.txt");
+
+    match locate_in(PuzzleId::new(2020, 1), &crate_dir, Some(&missing), None) {
+        Err(InputError::NotFound { tried, .. }) => assert_eq!(tried, vec![missing]),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_locate_in_a_year_directory() {
+    let crate_dir = scratch_dir("year-crate");
+    let input_dir = scratch_dir("year-inputs");
+    std::fs::create_dir_all(input_dir.join("2021")).unwrap();
+    std::fs::write(input_dir.join("day3.txt"), "2020").unwrap();
+    std::fs::write(input_dir.join("2021").join("day3.txt"), "2021").unwrap();
+
+    let found = locate_in(PuzzleId::new(2021, 3), &crate_dir, None, Some(&input_dir)).unwrap();
+    assert_eq!(found, input_dir.join("2021").join("day3.txt"));
+    let err = locate_in(PuzzleId::new(2021, 4), &crate_dir, None, Some(&input_dir)).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("couldn't find the input for 2021 day 4, tried:"));
+}
+
+#[test]
+fn test_source_from_arg() {
+    assert_eq!(Source::from("-"), Source::Stdin);
+    assert_eq!(
+        Source::from("in.txt"),
+        Source::File(PathBuf::from("in.txt"))
+    );
+}
+
+#[test]
+fn test_read_labels_inputs_with_their_path() {
+    let crate_dir = scratch_dir("read");
+    let other = crate_dir.join("other.txt");
+    std::fs::write(crate_dir.join("input.txt"), "default").unwrap();
+    std::fs::write(&other, "other").unwrap();
+
+    let input = read(
+        PuzzleId::new(2020, 2),
+        &crate_dir,
+        Some(&Source::File(other.clone())),
+    )
+    .unwrap();
+    assert_eq!(input.contents, "other");
+    assert_eq!(input.label, other.display().to_string());
+}
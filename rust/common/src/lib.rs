@@ -0,0 +1,158 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod fuzz;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod output;
+pub mod property;
+pub mod puzzle;
+pub mod random;
+pub mod records;
+pub mod scratch;
+pub mod trace;
+
+use error::ParseError;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// The steps every day goes through: parse the puzzle input once, then compute each part's
+/// answer from the parsed data.
+pub trait Solution {
+    /// What the input is parsed into. It may borrow from the input string.
+    type Parsed<'a>;
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer;
+}
+
+/// What a [`Found`] without an answer shows as.
+pub const NO_ANSWER: &str = "none";
+
+/// An answer that a valid input doesn't always have, like the entries of an expense report
+/// summing to 2020. It shows as [`NO_ANSWER`] when there is none.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Found<T>(pub Option<T>);
+
+impl<T: Display> Display for Found<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(answer) => answer.fmt(f),
+            None => f.write_str(NO_ANSWER),
+        }
+    }
+}
+
+/// The answer to one part, with the time spent parsing the input it came from and the time
+/// spent solving the part itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solved {
+    pub part: u8,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Parses `input` a single time and solves each of the requested `parts` (1 or 2), returning
+/// the answers in the same order. Each phase is a [`trace`] span, named `parse`, `part1` and
+/// `part2`.
+pub fn solve<S: Solution>(input: &str, parts: &[u8]) -> Result<Vec<Solved>, ParseError> {
+    let start = Instant::now();
+    let parsed = trace::in_span("parse", || S::parse(input))?;
+    let parse_time = start.elapsed();
+    let solved = parts
+        .iter()
+        .map(|&part| {
+            let _span = trace::span(if part == 1 { "part1" } else { "part2" });
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&parsed).to_string(),
+                2 => S::part2(&parsed).to_string(),
+                _ => panic!("there is no part {}", part),
+            };
+            trace::event(|| format!("answer {}", answer));
+            Solved {
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(solved)
+}
+
+#[cfg(test)]
+struct LineCount;
+
+#[cfg(test)]
+impl Solution for LineCount {
+    type Parsed<'a> = Vec<&'a str>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        trace::count("records", lines.len() as u64);
+        Ok(lines)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.len()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.iter().map(|line| line.len()).sum()
+    }
+}
+
+#[cfg(test)]
+fn answers(solved: Result<Vec<Solved>, ParseError>) -> Vec<(u8, String)> {
+    solved
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.part, s.answer))
+        .collect()
+}
+
+#[test]
+fn test_solve() {
+    assert_eq!(
+        answers(solve::<LineCount>("ab\ncde", &[1, 2])),
+        vec![(1, "2".to_string()), (2, "5".to_string())]
+    );
+    assert_eq!(
+        answers(solve::<LineCount>("ab\ncde", &[2])),
+        vec![(2, "5".to_string())]
+    );
+}
+
+#[test]
+fn test_solve_is_traced() {
+    trace::start();
+    solve::<LineCount>("ab\ncde", &[2]).unwrap();
+    let trace = trace::finish().unwrap();
+    let spans: Vec<(&str, &[(&str, u64)])> = trace
+        .spans
+        .iter()
+        .map(|span| (span.name.as_str(), &span.counters[..]))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![("parse", &[("records", 2)][..]), ("part2", &[])]
+    );
+    assert_eq!(trace.events[0].message, "answer 5");
+    assert_eq!(trace.events[0].span, Some(1));
+}
+
+#[test]
+fn test_found() {
+    assert_eq!(Found(Some(514579)).to_string(), "514579");
+    assert_eq!(Found::<i32>(None).to_string(), "none");
+}
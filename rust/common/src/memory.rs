@@ -0,0 +1,222 @@
+use crate::error::ParseError;
+use crate::Solution;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+
+/// A global allocator that hands every request to the system allocator and counts it. It only
+/// counts when a binary installs it:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+///
+/// Counts are kept per thread, so [`measure`] only sees the allocations of the code it runs.
+pub struct CountingAllocator;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    /// Bytes allocated and not freed yet. Memory freed by another thread than the one that
+    /// allocated it can make it negative.
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Records an allocation of `allocated` bytes that also freed `freed` bytes, like a `realloc`.
+fn record(allocated: usize, freed: usize) {
+    // Allocations can happen while the thread is torn down, when its counters are gone.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated as u64;
+        }
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// What a piece of code allocated. A `realloc` counts as one allocation of its new size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The most memory that was in use at once on top of what was in use before.
+    pub peak: u64,
+}
+
+/// Runs `f` and counts what it allocates on the current thread. Everything is zero when the
+/// [`CountingAllocator`] isn't installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.live;
+        counters.set(c);
+        c
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Whether the [`CountingAllocator`] is the global allocator of this program.
+pub fn is_installed() -> bool {
+    let (_, stats) = measure(|| black_box(Box::new(0u8)));
+    stats.allocations > 0
+}
+
+/// The allocations of a single named phase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllocProfile {
+    pub name: String,
+    pub stats: AllocStats,
+}
+
+/// Counts the allocations of each phase of a solution: `parse`, then `part1` and `part2` on
+/// the parsed input.
+pub fn profile_solution<S: Solution>(input: &str) -> Result<Vec<AllocProfile>, ParseError> {
+    let (parsed, parse) = measure(|| S::parse(input));
+    let parsed = parsed?;
+    let (_, part1) = measure(|| S::part1(&parsed));
+    let (_, part2) = measure(|| S::part2(&parsed));
+    Ok(vec![
+        AllocProfile {
+            name: "parse".to_string(),
+            stats: parse,
+        },
+        AllocProfile {
+            name: "part1".to_string(),
+            stats: part1,
+        },
+        AllocProfile {
+            name: "part2".to_string(),
+            stats: part2,
+        },
+    ])
+}
+
+/// Formats the profiles as an aligned, human-readable table.
+pub fn table(profiles: &[AllocProfile]) -> String {
+    let name_width = profiles
+        .iter()
+        .map(|profile| profile.name.len())
+        .chain(std::iter::once("name".len()))
+        .max()
+        .unwrap_or(0);
+    let mut table = format!(
+        "{:<name_width$}  {:>12}  {:>14}  {:>14}\n",
+        "name",
+        "allocations",
+        "bytes",
+        "peak bytes",
+        name_width = name_width,
+    );
+    for profile in profiles {
+        table.push_str(&format!(
+            "{:<name_width$}  {:>12}  {:>14}  {:>14}\n",
+            profile.name,
+            profile.stats.allocations,
+            profile.stats.bytes,
+            profile.stats.peak,
+            name_width = name_width,
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    assert!(is_installed());
+
+    let (v, stats) = measure(|| vec![0u8; 100]);
+    assert_eq!(
+        stats,
+        AllocStats {
+            allocations: 1,
+            bytes: 100,
+            peak: 100
+        }
+    );
+
+    let (_, stats) = measure(|| {
+        let mut v = Vec::<u8>::with_capacity(10);
+        v.extend_from_slice(&[1; 20]);
+        drop(v);
+        black_box(vec![0u8; 5])
+    });
+    assert_eq!(stats.allocations, 3);
+    assert_eq!(stats.peak, 20);
+    drop(v);
+
+    let (_, stats) = measure(|| 1 + 1);
+    assert_eq!(stats, AllocStats::default());
+}
+
+#[test]
+fn test_profile_solution() {
+    let profiles = profile_solution::<crate::LineCount>("ab\ncde\nf").unwrap();
+    let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["parse", "part1", "part2"]);
+    assert_eq!(profiles[0].stats.allocations, 1);
+    assert!(profiles[0].stats.peak >= 3 * std::mem::size_of::<&str>() as u64);
+    assert_eq!(profiles[1].stats, AllocStats::default());
+
+    let table = table(&profiles);
+    assert!(table.starts_with("name    allocations"));
+    assert_eq!(table.lines().count(), 4);
+}
@@ -0,0 +1,163 @@
+use crate::puzzle::PuzzleId;
+use crate::Solved;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How answers are printed. `Human` is whatever prose each day prints; the others are meant for
+/// scripts and print one [`Record`] per line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Human,
+    /// One JSON object per line.
+    Json,
+    /// Tab-separated values, with a header line.
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            unknown => Err(format!(
+                "unknown format: {} (expected human, json or tsv)",
+                unknown
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// The line to print before the first record, if the format has one.
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some("year\tday\tpart\tanswer\tparse_ns\tsolve_ns\tinput"),
+            _ => None,
+        }
+    }
+
+    /// A single line for `record`. The human format has no record lines of its own, so it
+    /// falls back to a short sentence.
+    pub fn record(&self, record: &Record) -> String {
+        match self {
+            Format::Human => format!(
+                "{} part {}: {} ({:?} parsing, {:?} solving)",
+                PuzzleId::new(record.year, record.day),
+                record.part, record.answer, record.parse_time, record.solve_time
+            ),
+            Format::Json => format!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{},\"input\":{}}}",
+                record.year,
+                record.day,
+                record.part,
+                json_string(&record.answer),
+                record.parse_time.as_nanos(),
+                record.solve_time.as_nanos(),
+                json_string(&record.input)
+            ),
+            Format::Tsv => format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                record.year,
+                record.day,
+                record.part,
+                tsv_field(&record.answer),
+                record.parse_time.as_nanos(),
+                record.solve_time.as_nanos(),
+                tsv_field(&record.input)
+            ),
+        }
+    }
+}
+
+/// A single answer along with where it came from and how long it took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// What identifies the input, usually its path.
+    pub input: String,
+}
+
+impl Record {
+    pub fn new(puzzle: PuzzleId, input: &str, solved: Solved) -> Self {
+        Record {
+            year: puzzle.year,
+            day: puzzle.day,
+            part: solved.part,
+            answer: solved.answer,
+            parse_time: solved.parse_time,
+            solve_time: solved.solve_time,
+            input: input.to_string(),
+        }
+    }
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn tsv_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+fn example_record() -> Record {
+    Record {
+        year: 2020,
+        day: 4,
+        part: 2,
+        answer: "194".to_string(),
+        parse_time: Duration::from_micros(12),
+        solve_time: Duration::from_millis(3),
+        input: "day4/\"my\" input.txt".to_string(),
+    }
+}
+
+#[test]
+fn test_json_record() {
+    assert_eq!(
+        Format::Json.record(&example_record()),
+        r#"{"year":2020,"day":4,"part":2,"answer":"194","parse_ns":12000,"solve_ns":3000000,"input":"day4/\"my\" input.txt"}"#
+    );
+}
+
+#[test]
+fn test_tsv_record() {
+    assert_eq!(
+        Format::Tsv.header(),
+        Some("year\tday\tpart\tanswer\tparse_ns\tsolve_ns\tinput")
+    );
+    assert_eq!(
+        Format::Tsv.record(&example_record()),
+        "2020\t4\t2\t194\t12000\t3000000\tday4/\"my\" input.txt"
+    );
+}
+
+#[test]
+fn test_parse_format() {
+    assert_eq!("json".parse(), Ok(Format::Json));
+    assert_eq!("human".parse(), Ok(Format::Human));
+    assert!("xml".parse::<Format>().is_err());
+}
@@ -0,0 +1,230 @@
+use crate::fuzz::{catch_quietly, FuzzConfig, SEED_ENV};
+use crate::random::Rng;
+use std::fmt::{self, Debug};
+
+/// A value a property doesn't hold for, with the smallest value it was shrunk to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Counterexample<T> {
+    pub name: String,
+    pub seed: u64,
+    /// The case, counted from 0, that found the value.
+    pub case: usize,
+    pub original: T,
+    pub minimal: T,
+    /// Why the property doesn't hold for the minimal value.
+    pub message: String,
+}
+
+impl<T: Debug> fmt::Display for Counterexample<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} failed on case {} of seed {}: {}\n  minimal: {:#?}\n  original: {:?}\n  \
+             run again with {}={}",
+            self.name,
+            self.case,
+            self.seed,
+            self.message,
+            self.minimal,
+            self.original,
+            SEED_ENV,
+            self.seed
+        )
+    }
+}
+
+/// What a property says about a value: nothing when it holds, why when it doesn't.
+pub type Verdict = Result<(), String>;
+
+/// Passes when `actual` and `expected` are equal, naming `what` was compared when they aren't.
+pub fn equal<T: PartialEq + Debug>(what: &str, actual: T, expected: T) -> Verdict {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}: got {:?}, expected {:?}",
+            what, actual, expected
+        ))
+    }
+}
+
+/// Checks `property` on `config.cases` values made by `generate`. A property fails by returning
+/// an error or by panicking. The first failing value is shrunk: `shrink` gives simpler versions
+/// of a value, the first one that still fails replaces it, until none does.
+pub fn check<T: Clone>(
+    name: &str,
+    config: &FuzzConfig,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> Verdict,
+) -> Option<Counterexample<T>> {
+    let verdict = |value: &T| catch_quietly(|| property(value)).and_then(|verdict| verdict);
+    let mut rng = Rng::new(config.seed);
+    for case in 0..config.cases {
+        let original = generate(&mut rng);
+        let mut message = match verdict(&original) {
+            Ok(()) => continue,
+            Err(message) => message,
+        };
+        let mut minimal = original.clone();
+        while let Some((simpler, why)) = shrink(&minimal)
+            .into_iter()
+            .find_map(|simpler| verdict(&simpler).err().map(|why| (simpler, why)))
+        {
+            minimal = simpler;
+            message = why;
+        }
+        return Some(Counterexample {
+            name: name.to_string(),
+            seed: config.seed,
+            case,
+            original,
+            minimal,
+            message,
+        });
+    }
+    None
+}
+
+/// Same as [`check`], panicking with the counterexample. Meant to be called from a test.
+pub fn assert_property<T: Clone + Debug>(
+    name: &str,
+    config: &FuzzConfig,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> Verdict,
+) {
+    if let Some(counterexample) = check(name, config, generate, shrink, property) {
+        panic!("{}", counterexample);
+    }
+}
+
+/// Simpler numbers than `value`: `toward` itself, then halfway to it, then one step closer.
+pub fn shrink_number(value: i64, toward: i64) -> Vec<i64> {
+    let mut simpler = Vec::new();
+    if value == toward {
+        return simpler;
+    }
+    simpler.push(toward);
+    let halfway = toward + (value - toward) / 2;
+    if halfway != toward && halfway != value {
+        simpler.push(halfway);
+    }
+    let step = if value > toward { value - 1 } else { value + 1 };
+    if !simpler.contains(&step) {
+        simpler.push(step);
+    }
+    simpler
+}
+
+/// Simpler lists than `items`: without their first or second half, without one of the items,
+/// then with one of the items shrunk by `shrink_item`.
+pub fn shrink_vec<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut simpler = Vec::new();
+    let half = items.len() / 2;
+    if half > 0 {
+        simpler.push(items[half..].to_vec());
+        simpler.push(items[..half].to_vec());
+    }
+    for index in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(index);
+        simpler.push(without);
+    }
+    for (index, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut with = items.to_vec();
+            with[index] = smaller;
+            simpler.push(with);
+        }
+    }
+    simpler
+}
+
+/// Simpler strings than `text`: shorter ones, and ones where a character became `simplest`.
+pub fn shrink_string(text: &str, simplest: char) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    shrink_vec(&chars, |&c| {
+        if c == simplest {
+            vec![]
+        } else {
+            vec![simplest]
+        }
+    })
+    .into_iter()
+    .map(|chars| chars.into_iter().collect())
+    .collect()
+}
+
+#[cfg(test)]
+const CONFIG: FuzzConfig = FuzzConfig {
+    seed: 9,
+    cases: 1000,
+};
+
+#[test]
+fn test_check_shrinks_to_the_smallest_counterexample() {
+    // "Lists are sorted" is false, and the smallest list that shows it is two items.
+    let counterexample = check(
+        "sorted",
+        &CONFIG,
+        |rng| {
+            (0..rng.below(20))
+                .map(|_| rng.range(0, 100))
+                .collect::<Vec<i64>>()
+        },
+        |items| shrink_vec(items, |&n| shrink_number(n, 0)),
+        |items| {
+            if items.windows(2).all(|pair| pair[0] <= pair[1]) {
+                Ok(())
+            } else {
+                Err("not sorted".to_string())
+            }
+        },
+    )
+    .unwrap();
+    assert_eq!(counterexample.minimal, vec![1, 0]);
+    assert_eq!(counterexample.message, "not sorted");
+    assert!(counterexample
+        .to_string()
+        .contains("run again with AOC_FUZZ_SEED=9"));
+}
+
+#[test]
+fn test_check_catches_panics() {
+    let counterexample = check(
+        "short strings",
+        &CONFIG,
+        |rng| "x".repeat(rng.below(50)),
+        |text| shrink_string(text, 'a'),
+        |text| {
+            assert!(text.len() < 10, "too long");
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(counterexample.minimal, "aaaaaaaaaa");
+    assert_eq!(counterexample.message, "too long");
+}
+
+#[test]
+fn test_check_passing_property() {
+    let property = |&(a, b): &(i64, i64)| equal("a + b", a + b, b + a);
+    let generate = |rng: &mut Rng| (rng.range(-50, 50), rng.range(-50, 50));
+    assert_eq!(
+        check("commutative", &CONFIG, generate, |_| vec![], property),
+        None
+    );
+    assert_eq!(
+        equal("sum", 1, 2),
+        Err("sum: got 1, expected 2".to_string())
+    );
+}
+
+#[test]
+fn test_shrink_number() {
+    assert_eq!(shrink_number(10, 0), vec![0, 5, 9]);
+    assert_eq!(shrink_number(-3, 0), vec![0, -1, -2]);
+    assert_eq!(shrink_number(1, 0), vec![0]);
+    assert!(shrink_number(4, 4).is_empty());
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The year this workspace started with. Its days keep the layout they had before there were
+/// other years: crates named `dayN` at the root of the workspace.
+pub const DEFAULT_YEAR: u16 = 2020;
+
+/// The first year of Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// A puzzle, identified by its year and day. Everything about a day, from its crate to its input
+/// and its answers, is found from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl PuzzleId {
+    pub const fn new(year: u16, day: u8) -> Self {
+        PuzzleId { year, day }
+    }
+
+    /// The name of the day's crate: `dayN` for the default year, `dayN_YEAR` for the others,
+    /// since package names are unique in a workspace.
+    pub fn crate_name(&self) -> String {
+        if self.year == DEFAULT_YEAR {
+            format!("day{}", self.day)
+        } else {
+            format!("day{}_{}", self.day, self.year)
+        }
+    }
+
+    /// Where the day's crate is, relative to the workspace: `dayN` for the default year,
+    /// `YEAR/dayN` for the others.
+    pub fn crate_path(&self) -> PathBuf {
+        let dir = PathBuf::from(format!("day{}", self.day));
+        if self.year == DEFAULT_YEAR {
+            dir
+        } else {
+            PathBuf::from(self.year.to_string()).join(dir)
+        }
+    }
+
+    /// The name of the day's input in a directory of inputs: `dayN.txt` for the default year,
+    /// `YEAR/dayN.txt` for the others.
+    pub fn input_name(&self) -> PathBuf {
+        self.crate_path().with_extension("txt")
+    }
+
+    /// How the puzzle is written on the command line, the way [`FromStr`] reads it back: `N` for
+    /// the default year, `YEAR/N` for the others.
+    pub fn label(&self) -> String {
+        if self.year == DEFAULT_YEAR {
+            self.day.to_string()
+        } else {
+            format!("{}/{}", self.year, self.day)
+        }
+    }
+}
+
+impl fmt::Display for PuzzleId {
+    /// `day N`, with the year in front when it isn't the default one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year != DEFAULT_YEAR {
+            write!(f, "{} ", self.year)?;
+        }
+        write!(f, "day {}", self.day)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = String;
+
+    /// Reads `N` as day N of the default year and `YEAR/N` as day N of that year.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('/') {
+            Some((year, day)) => (parse_year(year)?, day),
+            None => (DEFAULT_YEAR, s),
+        };
+        match day.parse() {
+            Ok(day) => Ok(PuzzleId::new(year, day)),
+            Err(_) => Err(format!("invalid day: {}", s)),
+        }
+    }
+}
+
+/// Reads a year, from the first one of Advent of Code on.
+pub fn parse_year(year: &str) -> Result<u16, String> {
+    match year.parse() {
+        Ok(year) if year >= FIRST_YEAR => Ok(year),
+        _ => Err(format!("invalid year: {}", year)),
+    }
+}
+
+#[test]
+fn test_default_year_keeps_its_layout() {
+    let puzzle = PuzzleId::new(DEFAULT_YEAR, 4);
+    assert_eq!(puzzle.crate_name(), "day4");
+    assert_eq!(puzzle.crate_path(), PathBuf::from("day4"));
+    assert_eq!(puzzle.input_name(), PathBuf::from("day4.txt"));
+    assert_eq!(puzzle.to_string(), "day 4");
+    assert_eq!(puzzle.label(), "4");
+}
+
+#[test]
+fn test_other_years() {
+    let puzzle = PuzzleId::new(2021, 12);
+    assert_eq!(puzzle.crate_name(), "day12_2021");
+    assert_eq!(puzzle.crate_path(), PathBuf::from("2021").join("day12"));
+    assert_eq!(puzzle.input_name(), PathBuf::from("2021").join("day12.txt"));
+    assert_eq!(puzzle.to_string(), "2021 day 12");
+    assert_eq!(puzzle.label(), "2021/12");
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("3".parse(), Ok(PuzzleId::new(2020, 3)));
+    assert_eq!("2021/3".parse(), Ok(PuzzleId::new(2021, 3)));
+    for puzzle in [PuzzleId::new(2020, 25), PuzzleId::new(2022, 1)] {
+        assert_eq!(puzzle.label().parse(), Ok(puzzle));
+    }
+    assert!("four".parse::<PuzzleId>().is_err());
+    assert!("2021/".parse::<PuzzleId>().is_err());
+    assert!("1999/3".parse::<PuzzleId>().is_err());
+}
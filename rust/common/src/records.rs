@@ -0,0 +1,179 @@
+use crate::trace;
+use std::io::{self, BufRead};
+
+/// A group of consecutive non-blank lines, like a passport in day 4. Blank lines, including
+/// lines of only whitespace, separate records.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Record<'a> {
+    /// The 1-based line of the input the record starts on.
+    pub first_line: usize,
+    text: &'a str,
+}
+
+impl<'a> Record<'a> {
+    /// The record as it appears in the input, from the start of its first line to the end of
+    /// its last one, without trailing whitespace.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The lines of the record, without their line endings and trailing whitespace.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines().map(str::trim_end)
+    }
+}
+
+/// Splits `input` into [`Record`]s, lazily and borrowing from it. `\n` and `\r\n` line endings
+/// both work. Each record counts as one of the `records` of the current [`trace`] span.
+pub fn records(input: &str) -> Records<'_> {
+    Records {
+        rest: input,
+        line: 0,
+    }
+}
+
+pub struct Records<'a> {
+    rest: &'a str,
+    /// How many lines of the input were consumed so far.
+    line: usize,
+}
+
+impl<'a> Records<'a> {
+    /// Consumes the next line if `wanted` says so.
+    fn next_line_if(&mut self, wanted: impl Fn(&str) -> bool) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (line, rest) = match self.rest.find('\n') {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => (self.rest, ""),
+        };
+        if !wanted(line) {
+            return None;
+        }
+        self.rest = rest;
+        self.line += 1;
+        Some(line)
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_line_if(is_blank).is_some() {}
+        let start = self.rest;
+        let first_line = self.line + 1;
+        let mut len = 0;
+        while let Some(line) = self.next_line_if(|line| !is_blank(line)) {
+            len = line.as_ptr() as usize - start.as_ptr() as usize + line.trim_end().len();
+        }
+        if len == 0 {
+            return None;
+        }
+        trace::count("records", 1);
+        Some(Record {
+            first_line,
+            text: &start[..len],
+        })
+    }
+}
+
+/// A [`Record`] read from a [`BufRead`], which owns its lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordBuf {
+    /// The 1-based line of the input the record starts on.
+    pub first_line: usize,
+    /// The lines of the record, without their line endings and trailing whitespace.
+    pub lines: Vec<String>,
+}
+
+/// Like [`records`], but reading lazily from `reader`, one record at a time.
+pub fn read_records<R: BufRead>(reader: R) -> ReadRecords<R> {
+    ReadRecords { reader, line: 0 }
+}
+
+pub struct ReadRecords<R> {
+    reader: R,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for ReadRecords<R> {
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<RecordBuf> = None;
+        let mut buffer = String::new();
+        loop {
+            buffer.clear();
+            match self.reader.read_line(&mut buffer) {
+                Ok(0) => return record.map(Ok),
+                Ok(_) => self.line += 1,
+                Err(err) => return Some(Err(err)),
+            }
+            if is_blank(&buffer) {
+                if record.is_some() {
+                    return record.map(Ok);
+                }
+                continue;
+            }
+            let first_line = self.line;
+            record
+                .get_or_insert_with(|| RecordBuf {
+                    first_line,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(buffer.trim_end().to_string());
+        }
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+#[cfg(test)]
+fn summary<'a>(records: impl Iterator<Item = Record<'a>>) -> Vec<(usize, Vec<&'a str>)> {
+    records
+        .map(|record| (record.first_line, record.lines().collect()))
+        .collect()
+}
+
+#[test]
+fn test_records() {
+    let input = "a b\nc\n\nd\n";
+    assert_eq!(
+        summary(records(input)),
+        vec![(1, vec!["a b", "c"]), (4, vec!["d"])]
+    );
+    assert_eq!(records(input).next().unwrap().text(), "a b\nc");
+    assert_eq!(summary(records("")), vec![]);
+    assert_eq!(summary(records("\n  \n")), vec![]);
+}
+
+#[test]
+fn test_records_crlf_and_whitespace() {
+    let input = "\r\n\r\n  a \r\nb\t\r\n \t\r\n\r\nc\r\nd";
+    assert_eq!(
+        summary(records(input)),
+        vec![(3, vec!["  a", "b"]), (7, vec!["c", "d"])]
+    );
+    assert_eq!(records(input).next().unwrap().text(), "  a \r\nb");
+}
+
+#[test]
+fn test_read_records() {
+    let input = "\r\n  a \r\nb\t\r\n \t\r\n\r\nc\r\nd";
+    let read: Vec<RecordBuf> = read_records(io::Cursor::new(input))
+        .collect::<io::Result<_>>()
+        .unwrap();
+    let borrowed: Vec<RecordBuf> = records(input)
+        .map(|record| RecordBuf {
+            first_line: record.first_line,
+            lines: record.lines().map(String::from).collect(),
+        })
+        .collect();
+    assert_eq!(read, borrowed);
+    assert_eq!(read[1].first_line, 6);
+}
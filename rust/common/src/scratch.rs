@@ -0,0 +1,55 @@
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// An empty directory for a test to write into, removed with everything in it when dropped.
+#[derive(Debug)]
+pub struct ScratchDir {
+    path: PathBuf,
+}
+
+impl ScratchDir {
+    /// Creates `aoc-<name>-<pid>` in the temporary directory, emptying it if a previous run left
+    /// it behind. `name` must be unique among the tests running at the same time.
+    ///
+    /// Panics when it can't be created.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path)
+            .unwrap_or_else(|err| panic!("couldn't create {}: {}", path.display(), err));
+        ScratchDir { path }
+    }
+}
+
+impl Deref for ScratchDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for ScratchDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn test_scratch_dir_is_removed() {
+    let dir = ScratchDir::new("scratch");
+    fs::write(dir.join("file.txt"), "contents").unwrap();
+    let path = dir.to_path_buf();
+    assert!(path.join("file.txt").exists());
+    drop(dir);
+    assert!(!path.exists());
+}
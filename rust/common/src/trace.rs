@@ -0,0 +1,302 @@
+use crate::output::json_string;
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// A named phase of the work, like `parse` or `part1`, with the counters that were bumped while
+/// it was the innermost open span.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub name: String,
+    /// The index of the span this one was opened in, if any.
+    pub parent: Option<usize>,
+    /// When the span was opened, from the start of the trace.
+    pub start: Duration,
+    pub duration: Duration,
+    pub counters: Vec<(&'static str, u64)>,
+}
+
+/// Something that happened at one point in time, inside `span` if there was one open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub message: String,
+    pub span: Option<usize>,
+    pub at: Duration,
+}
+
+/// Everything recorded between [`start`] and [`finish`]. Spans are in the order they were
+/// opened, so a parent always comes before its children.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trace {
+    pub spans: Vec<Span>,
+    pub events: Vec<Event>,
+}
+
+struct Tracer {
+    origin: Instant,
+    trace: Trace,
+    /// The spans that are open, innermost last.
+    open: Vec<usize>,
+}
+
+thread_local! {
+    static TRACER: RefCell<Option<Tracer>> = const { RefCell::new(None) };
+}
+
+/// Starts recording spans, counters and events on the current thread, dropping anything
+/// recorded before. Until then, and after [`finish`], they cost a thread-local lookup and
+/// record nothing.
+pub fn start() {
+    TRACER.with(|tracer| {
+        *tracer.borrow_mut() = Some(Tracer {
+            origin: Instant::now(),
+            trace: Trace::default(),
+            open: Vec::new(),
+        })
+    });
+}
+
+/// Stops recording and returns what was recorded, if [`start`] was called.
+pub fn finish() -> Option<Trace> {
+    TRACER.with(|tracer| tracer.borrow_mut().take().map(|tracer| tracer.trace))
+}
+
+pub fn is_enabled() -> bool {
+    TRACER.with(|tracer| tracer.borrow().is_some())
+}
+
+fn with_tracer<T>(f: impl FnOnce(&mut Tracer) -> T) -> Option<T> {
+    // Like the allocation counters, tracing can be asked for while the thread is torn down.
+    TRACER
+        .try_with(|tracer| tracer.borrow_mut().as_mut().map(f))
+        .ok()
+        .flatten()
+}
+
+/// Closes its span when dropped.
+#[must_use = "the span is closed as soon as its guard is dropped"]
+pub struct SpanGuard {
+    index: Option<usize>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(index) = self.index {
+            with_tracer(|tracer| {
+                let end = tracer.origin.elapsed();
+                let span = &mut tracer.trace.spans[index];
+                span.duration = end - span.start;
+                tracer.open.retain(|&open| open != index);
+            });
+        }
+    }
+}
+
+/// Opens a span inside the innermost open one. It lasts until the guard is dropped.
+pub fn span(name: &str) -> SpanGuard {
+    let index = with_tracer(|tracer| {
+        let index = tracer.trace.spans.len();
+        tracer.trace.spans.push(Span {
+            name: name.to_string(),
+            parent: tracer.open.last().copied(),
+            start: tracer.origin.elapsed(),
+            duration: Duration::ZERO,
+            counters: Vec::new(),
+        });
+        tracer.open.push(index);
+        index
+    });
+    SpanGuard { index }
+}
+
+/// Runs `f` in a span named `name`.
+pub fn in_span<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let _span = span(name);
+    f()
+}
+
+/// Adds `n` to the counter `name` of the innermost open span. Counts made outside of any span
+/// are dropped.
+pub fn count(name: &'static str, n: u64) {
+    with_tracer(|tracer| {
+        if let Some(&index) = tracer.open.last() {
+            let counters = &mut tracer.trace.spans[index].counters;
+            match counters.iter_mut().find(|(counter, _)| *counter == name) {
+                Some((_, total)) => *total += n,
+                None => counters.push((name, n)),
+            }
+        }
+    });
+}
+
+/// Records an event in the innermost open span. `message` is only called when tracing.
+pub fn event(message: impl FnOnce() -> String) {
+    with_tracer(|tracer| {
+        let event = Event {
+            message: message(),
+            span: tracer.open.last().copied(),
+            at: tracer.origin.elapsed(),
+        };
+        tracer.trace.events.push(event);
+    });
+}
+
+fn micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_nanos() as f64 / 1000.0)
+}
+
+impl Trace {
+    fn depth(&self, mut span: Option<usize>) -> usize {
+        let mut depth = 0;
+        while let Some(index) = span {
+            depth += 1;
+            span = self.spans[index].parent;
+        }
+        depth
+    }
+
+    /// The spans as an indented tree, one per line with its duration and counters, and the
+    /// events where they happened.
+    pub fn tree(&self) -> String {
+        let mut lines: Vec<(Duration, String)> = Vec::new();
+        for (index, span) in self.spans.iter().enumerate() {
+            let indent = "  ".repeat(self.depth(Some(index)) - 1);
+            let mut line = format!(
+                "{:<32} {:>12}",
+                format!("{}{}", indent, span.name),
+                format!("{:?}", span.duration)
+            );
+            for (counter, n) in &span.counters {
+                write!(line, "  {}={}", counter, n).unwrap();
+            }
+            lines.push((span.start, line));
+        }
+        for event in &self.events {
+            let indent = "  ".repeat(self.depth(event.span));
+            lines.push((event.at, format!("{}* {}", indent, event.message)));
+        }
+        // Stable, so a span stays in front of what happened right as it was opened.
+        lines.sort_by_key(|(at, _)| *at);
+        lines.into_iter().map(|(_, line)| line + "\n").collect()
+    }
+
+    /// The trace in the JSON format of the Chrome trace viewer, which `chrome://tracing` and
+    /// https://ui.perfetto.dev open. Times are in microseconds and counters are the arguments
+    /// of their span.
+    pub fn chrome_json(&self) -> String {
+        let spans = self.spans.iter().map(|span| {
+            let args: Vec<String> = span
+                .counters
+                .iter()
+                .map(|(counter, n)| format!("{}:{}", json_string(counter), n))
+                .collect();
+            format!(
+                "{{\"name\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1,\"args\":{{{}}}}}",
+                json_string(&span.name),
+                micros(span.start),
+                micros(span.duration),
+                args.join(",")
+            )
+        });
+        let events = self.events.iter().map(|event| {
+            format!(
+                "{{\"name\":{},\"ph\":\"i\",\"s\":\"t\",\"ts\":{},\"pid\":1,\"tid\":1}}",
+                json_string(&event.message),
+                micros(event.at)
+            )
+        });
+        let events: Vec<String> = spans.chain(events).collect();
+        format!("{{\"traceEvents\":[\n{}\n]}}\n", events.join(",\n"))
+    }
+}
+
+#[test]
+fn test_nothing_is_recorded_when_off() {
+    assert!(!is_enabled());
+    let _span = span("parse");
+    count("records", 3);
+    event(|| unreachable!("messages aren't made when tracing is off"));
+    assert_eq!(finish(), None);
+}
+
+#[test]
+fn test_nested_spans() {
+    start();
+    assert!(is_enabled());
+    count("records", 1);
+    in_span("day 4", || {
+        in_span("parse", || {
+            count("records", 2);
+            count("records", 3);
+            count("fields", 7);
+        });
+        in_span("part1", || event(|| "answer 2".to_string()));
+    });
+    let trace = finish().unwrap();
+    assert!(!is_enabled());
+
+    let names: Vec<(&str, Option<usize>)> = trace
+        .spans
+        .iter()
+        .map(|span| (span.name.as_str(), span.parent))
+        .collect();
+    assert_eq!(
+        names,
+        vec![("day 4", None), ("parse", Some(0)), ("part1", Some(0))]
+    );
+    assert_eq!(trace.spans[1].counters, vec![("records", 5), ("fields", 7)]);
+    assert!(trace.spans[0].counters.is_empty());
+    let (day, parse) = (&trace.spans[0], &trace.spans[1]);
+    assert!(parse.start >= day.start && parse.start + parse.duration <= day.start + day.duration);
+    assert_eq!(trace.events.len(), 1);
+    assert_eq!(trace.events[0].span, Some(2));
+}
+
+#[cfg(test)]
+fn example_trace() -> Trace {
+    Trace {
+        spans: vec![Span {
+            name: "read \"x\"".to_string(),
+            parent: None,
+            start: Duration::from_nanos(1500),
+            duration: Duration::from_micros(20),
+            counters: vec![("bytes", 12), ("lines", 2)],
+        }],
+        events: vec![Event {
+            message: "answer 7".to_string(),
+            span: Some(0),
+            at: Duration::from_micros(3),
+        }],
+    }
+}
+
+#[test]
+fn test_tree() {
+    let mut trace = example_trace();
+    trace.spans.push(Span {
+        name: "parse".to_string(),
+        parent: Some(0),
+        start: Duration::from_micros(2),
+        duration: Duration::from_micros(15),
+        counters: vec![("records", 2)],
+    });
+    assert_eq!(
+        trace.tree(),
+        format!(
+            "{:<32} {:>12}  bytes=12  lines=2\n{:<32} {:>12}  records=2\n  * answer 7\n",
+            "read \"x\"", "20µs", "  parse", "15µs"
+        )
+    );
+}
+
+#[test]
+fn test_chrome_json() {
+    let trace = example_trace();
+    assert_eq!(
+        trace.chrome_json(),
+        "{\"traceEvents\":[\n\
+         {\"name\":\"read \\\"x\\\"\",\"ph\":\"X\",\"ts\":1.500,\"dur\":20.000,\"pid\":1,\"tid\":1,\"args\":{\"bytes\":12,\"lines\":2}},\n\
+         {\"name\":\"answer 7\",\"ph\":\"i\",\"s\":\"t\",\"ts\":3.000,\"pid\":1,\"tid\":1}\n\
+         ]}\n"
+    );
+}
@@ -0,0 +1,104 @@
+//! Day 1: Report Repair. Find the entries of an expense report that sum to 2020 and multiply
+//! them together.
+
+use common::error::{parse_number, Location, ParseError};
+use common::{trace, Found, Solution};
+use itertools::{process_results, Itertools};
+
+/// The expense report, one number per line. Part 1 looks for a pair of entries, part 2 for a
+/// triple.
+pub struct ExpenseReport;
+
+impl Solution for ExpenseReport {
+    type Parsed<'a> = Vec<i32>;
+    type Answer = Found<i128>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let numbers = numbers(&lines).collect::<Result<Vec<_>, _>>()?;
+        trace::count("records", numbers.len() as u64);
+        Ok(numbers)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        Found(entries_product(parsed.iter().copied(), 2))
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        Found(entries_product(parsed.iter().copied(), 3))
+    }
+}
+
+fn numbers<'a>(lines: &'a [&str]) -> impl Iterator<Item = Result<i32, ParseError>> + 'a {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, n)| parse_number(n, Location::new(index + 1, 1)))
+}
+
+/// The product of the first `comb` entries that sum to 2020, if there are any. Sums and
+/// products are computed without overflowing: pairs and triples always have one, and `None`
+/// also stands for a product of more entries that doesn't fit in an `i128`.
+fn entries_product(numbers: impl Iterator<Item = i32>, comb: usize) -> Option<i128> {
+    numbers
+        .combinations(comb)
+        .find(|numbers| numbers.iter().map(|&n| i64::from(n)).sum::<i64>() == 2020)
+        .and_then(|numbers| {
+            numbers
+                .into_iter()
+                .try_fold(1i128, |product, n| product.checked_mul(i128::from(n)))
+        })
+}
+
+/// Finds the `comb` entries that sum to 2020 and returns their product, taking ownership of the
+/// lines. A report without such entries has no answer.
+pub fn day1_no_borrow(lines: Vec<&str>, comb: usize) -> Result<Option<i128>, ParseError> {
+    process_results(numbers(&lines), |numbers| entries_product(numbers, comb))
+}
+
+/// Same as [`day1_no_borrow`], but only borrowing the lines.
+pub fn day1_borrow(lines: &[&str], comb: usize) -> Result<Option<i128>, ParseError> {
+    process_results(numbers(lines), |numbers| entries_product(numbers, comb))
+}
+
+#[test]
+fn test_day1() {
+    let base_input = vec!["1721", "979", "366", "299", "675", "1456"];
+    assert_eq!(day1_no_borrow(base_input.clone(), 2), Ok(Some(514579)));
+    assert_eq!(day1_no_borrow(base_input.clone(), 3), Ok(Some(241861950)));
+
+    assert_eq!(day1_borrow(&base_input, 2), Ok(Some(514579)));
+    assert_eq!(day1_borrow(&base_input, 3), Ok(Some(241861950)));
+}
+
+#[test]
+fn test_no_answer() {
+    assert_eq!(day1_borrow(&["1721", "979"], 3), Ok(None));
+    assert_eq!(ExpenseReport::part1(&vec![1, 2]).to_string(), "none");
+    assert_eq!(ExpenseReport::part2(&vec![]).to_string(), "none");
+    // Neither the sums nor the products of entries this big fit in an `i32`.
+    let huge = ["2147483647", "-2147481627", "2147483647", "-2147483647"];
+    assert_eq!(day1_borrow(&huge, 2), Ok(Some(2147483647 * -2147481627)));
+    assert_eq!(
+        day1_borrow(&huge, 4),
+        Ok(Some(2147483647 * -2147481627 * 2147483647 * -2147483647))
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        ExpenseReport::parse("1721\n97x9").err(),
+        Some(ParseError::InvalidNumber {
+            at: Location::new(2, 1),
+            text: "97x9".to_string(),
+        })
+    );
+    assert_eq!(
+        day1_borrow(&["1721", "x", "299"], 2),
+        Err(ParseError::InvalidNumber {
+            at: Location::new(2, 1),
+            text: "x".to_string(),
+        })
+    );
+}
@@ -0,0 +1,7 @@
+use day1::ExpenseReport;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<ExpenseReport>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}
@@ -0,0 +1,14 @@
+use day1::{day1_borrow, day1_no_borrow};
+
+const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+#[test]
+fn test_borrow_and_no_borrow_agree() {
+    let lines: Vec<&str> = EXAMPLE.lines().collect();
+    for comb in 2..=3 {
+        assert_eq!(
+            day1_borrow(&lines, comb),
+            day1_no_borrow(lines.clone(), comb)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use common::Solution;
+use day1::{day1_borrow, day1_no_borrow, ExpenseReport};
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("ExpenseReport", &config, &corpus, |input| {
+        if let Ok(parsed) = ExpenseReport::parse(input) {
+            let _ = ExpenseReport::part1(&parsed);
+            let _ = ExpenseReport::part2(&parsed);
+        }
+    });
+    assert_no_panics("day1_borrow", &config, &corpus, |input| {
+        let lines: Vec<&str> = input.lines().collect();
+        let _ = day1_borrow(&lines, 2);
+        let _ = day1_no_borrow(lines, 3);
+    });
+}
@@ -0,0 +1,54 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_number, shrink_vec};
+use common::random::Rng;
+use day1::{day1_borrow, day1_no_borrow};
+
+/// A list of entries with a pair summing to 2020 planted at random positions.
+fn report(rng: &mut Rng) -> Vec<i64> {
+    let mut entries: Vec<i64> = (0..rng.below(20)).map(|_| rng.range(0, 2020)).collect();
+    let first = rng.range(0, 2020);
+    entries.push(first);
+    entries.push(2020 - first);
+    rng.shuffle(&mut entries);
+    entries
+}
+
+/// The first `size` entries summing to 2020, in the order of their positions.
+fn naive(entries: &[i64], size: usize) -> Option<i64> {
+    let n = entries.len();
+    match size {
+        2 => (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .find(|&(i, j)| entries[i] + entries[j] == 2020)
+            .map(|(i, j)| entries[i] * entries[j]),
+        _ => (0..n)
+            .flat_map(|i| (i + 1..n).flat_map(move |j| (j + 1..n).map(move |k| (i, j, k))))
+            .find(|&(i, j, k)| entries[i] + entries[j] + entries[k] == 2020)
+            .map(|(i, j, k)| entries[i] * entries[j] * entries[k]),
+    }
+}
+
+#[test]
+fn test_finds_the_first_matching_entries() {
+    assert_property(
+        "day1 matches the naive search",
+        &FuzzConfig::from_env(),
+        report,
+        |entries| shrink_vec(entries, |&n| shrink_number(n, 0)),
+        |entries| {
+            let lines: Vec<String> = entries.iter().map(i64::to_string).collect();
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            for size in 2..=3 {
+                // Shrinking can take the planted entries apart, then there is no answer.
+                let expected = Ok(naive(entries, size).map(i128::from));
+                equal("day1_borrow", day1_borrow(&lines, size), expected.clone())?;
+                equal(
+                    "day1_no_borrow",
+                    day1_no_borrow(lines.clone(), size),
+                    expected,
+                )?;
+            }
+            Ok(())
+        },
+    );
+}
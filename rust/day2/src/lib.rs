@@ -0,0 +1,235 @@
+//! Day 2: Password Philosophy. Count the passwords from the corrupted database that follow the
+//! policy they were stored with.
+
+use common::error::{parse_lines, parse_number, Location, ParseError};
+use common::Solution;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use verex::{Expression, Verex};
+
+/// A line of the password database: a policy and the password it was stored with.
+pub struct RecoveredPassword {
+    pub policy: PasswordPolicy,
+    pub password: String,
+}
+
+impl RecoveredPassword {
+    const RECOVERED_PASSWORD_REGEX: Expression<'static> =
+        Expression::String("(\\d*)-(\\d*) (\\p{L}): (.*)");
+
+    /// Whether the password follows the policy as the sled rental place understands it.
+    pub fn validate_part1(&self) -> bool {
+        self.policy.validate_password_part1(&self.password)
+    }
+
+    /// Whether the password follows the policy as the Official Toboggan Corporate Policy
+    /// understands it.
+    pub fn validate_part2(&self) -> bool {
+        self.policy.validate_password_part2(&self.password)
+    }
+}
+
+/// The `min-max char` part of a database line.
+pub struct PasswordPolicy {
+    pub min: i32,
+    pub max: i32,
+    pub char: char,
+}
+
+impl PasswordPolicy {
+    /// `char` must appear between `min` and `max` times (inclusive) in the password.
+    pub fn validate_password_part1(&self, password: &str) -> bool {
+        let char_count = password.matches(self.char).count() as i32;
+        if char_count == 0 {
+            return false;
+        }
+        char_count >= self.min && char_count <= self.max
+    }
+
+    /// `char` must appear at exactly one of the (1-indexed) positions `min` and `max`.
+    /// Positions past the end of the password never match.
+    pub fn validate_password_part2(&self, password: &str) -> bool {
+        let matches = |position: i32| {
+            usize::try_from(position - 1)
+                .ok()
+                .and_then(|index| password.chars().nth(index))
+                == Some(self.char)
+        };
+
+        matches(self.min) ^ matches(self.max)
+    }
+}
+
+impl FromStr for RecoveredPassword {
+    type Err = ParseError;
+
+    /// Parses a line like `1-3 a: abcde`.
+    fn from_str(rec_pass: &str) -> Result<Self, Self::Err> {
+        let mut engine: Verex = Verex::new();
+        let compiled_regex = engine
+            .capture_expr(RecoveredPassword::RECOVERED_PASSWORD_REGEX)
+            .compile()
+            .expect("the password regex is valid");
+        let captures = compiled_regex
+            .captures(rec_pass)
+            .ok_or_else(|| ParseError::Unexpected {
+                at: Location::new(1, 1),
+                text: rec_pass.to_string(),
+                expected: "a line like `1-3 a: abcde`",
+            })?;
+        let position = |index| {
+            let text = captures.at(index).unwrap();
+            let position = parse_number(text, Location::of(1, rec_pass, text))?;
+            if position < 1 {
+                return Err(ParseError::Unexpected {
+                    at: Location::of(1, rec_pass, text),
+                    text: text.to_string(),
+                    expected: "a number of at least 1",
+                });
+            }
+            Ok(position)
+        };
+
+        let policy = PasswordPolicy {
+            min: position(2)?,
+            max: position(3)?,
+            char: captures.at(4).unwrap().chars().next().unwrap(),
+        };
+        let recovered = RecoveredPassword {
+            policy,
+            password: captures.at(5).unwrap().to_string(),
+        };
+        Ok(recovered)
+    }
+}
+
+impl Solution for RecoveredPassword {
+    type Parsed<'a> = Vec<RecoveredPassword>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.iter().filter(|p| p.validate_part1()).count()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.iter().filter(|p| p.validate_part2()).count()
+    }
+}
+
+#[test]
+fn test_check_password_part1() {
+    assert!(RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 1,
+            max: 3,
+            char: 'a',
+        },
+        password: String::from("abcde"),
+    }
+    .validate_part1());
+
+    assert!(!RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 1,
+            max: 3,
+            char: 'b',
+        },
+        password: String::from("cdefg"),
+    }
+    .validate_part1());
+
+    assert!(RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 2,
+            max: 9,
+            char: 'c',
+        },
+        password: String::from("ccccccccc"),
+    }
+    .validate_part1());
+}
+
+#[test]
+fn test_check_password_part2() {
+    assert!(RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 1,
+            max: 3,
+            char: 'a',
+        },
+        password: String::from("abcde"),
+    }
+    .validate_part2());
+
+    assert!(!RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 1,
+            max: 3,
+            char: 'b',
+        },
+        password: String::from("cdefg"),
+    }
+    .validate_part2());
+
+    assert!(!RecoveredPassword {
+        policy: PasswordPolicy {
+            min: 2,
+            max: 9,
+            char: 'c',
+        },
+        password: String::from("ccccccccc"),
+    }
+    .validate_part2());
+}
+
+#[test]
+fn test_recovered_password_parser() {
+    let rec_password = String::from("1-3 a: abcde");
+    let parsed_rec_password: RecoveredPassword = rec_password.parse().unwrap();
+    assert_eq!(parsed_rec_password.password, "abcde");
+    assert_eq!(parsed_rec_password.policy.char, 'a');
+    assert_eq!(parsed_rec_password.policy.min, 1);
+    assert_eq!(parsed_rec_password.policy.max, 3);
+}
+
+#[test]
+fn test_recovered_password_parse_errors() {
+    assert_eq!(
+        RecoveredPassword::parse("1-3 a: abcde\n1-3 a abcde").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(2, 1),
+            text: "1-3 a abcde".to_string(),
+            expected: "a line like `1-3 a: abcde`",
+        })
+    );
+    assert_eq!(
+        "-3 a: abcde".parse::<RecoveredPassword>().err(),
+        Some(ParseError::InvalidNumber {
+            at: Location::new(1, 1),
+            text: "".to_string(),
+        })
+    );
+    assert_eq!(
+        "1-0 a: abcde".parse::<RecoveredPassword>().err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(1, 3),
+            text: "0".to_string(),
+            expected: "a number of at least 1",
+        })
+    );
+}
+
+#[test]
+fn test_check_password_part2_out_of_range() {
+    let policy = PasswordPolicy {
+        min: 2,
+        max: 9,
+        char: 'c',
+    };
+    assert!(policy.validate_password_part2("acb"));
+    assert!(!policy.validate_password_part2("ab"));
+}
@@ -0,0 +1,7 @@
+use day2::RecoveredPassword;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<RecoveredPassword>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}
@@ -0,0 +1,19 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use common::Solution;
+use day2::RecoveredPassword;
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("RecoveredPassword::from_str", &config, &corpus, |input| {
+        if let Ok(password) = input.parse::<RecoveredPassword>() {
+            password.validate_part1();
+            password.validate_part2();
+        }
+    });
+    assert_no_panics("RecoveredPassword::parse", &config, &corpus, |input| {
+        let _ = RecoveredPassword::parse(input);
+    });
+}
@@ -0,0 +1,17 @@
+use day2::{PasswordPolicy, RecoveredPassword};
+
+#[test]
+fn test_policy_validation() {
+    let policy = PasswordPolicy {
+        min: 1,
+        max: 3,
+        char: 'a',
+    };
+    assert!(policy.validate_password_part1("abcde"));
+    assert!(policy.validate_password_part2("abcde"));
+    assert!(!policy.validate_password_part2("abade"));
+
+    let password: RecoveredPassword = "2-9 c: ccccccccc".parse().unwrap();
+    assert!(password.validate_part1());
+    assert!(!password.validate_part2());
+}
@@ -0,0 +1,87 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_number, shrink_string};
+use common::random::Rng;
+use day2::RecoveredPassword;
+
+/// The parts of a database line, generated apart so they can be shrunk apart.
+#[derive(Clone, Debug)]
+struct Line {
+    min: i64,
+    max: i64,
+    char: char,
+    password: String,
+}
+
+impl Line {
+    fn generate(rng: &mut Rng) -> Self {
+        let letters = ['a', 'b', 'c'];
+        Line {
+            min: rng.range(1, 12),
+            max: rng.range(1, 12),
+            char: *rng.pick(&letters),
+            password: (0..rng.below(16)).map(|_| *rng.pick(&letters)).collect(),
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut simpler = Vec::new();
+        for password in shrink_string(&self.password, 'a') {
+            simpler.push(Line {
+                password,
+                ..self.clone()
+            });
+        }
+        for min in shrink_number(self.min, 1) {
+            simpler.push(Line {
+                min,
+                ..self.clone()
+            });
+        }
+        for max in shrink_number(self.max, 1) {
+            simpler.push(Line {
+                max,
+                ..self.clone()
+            });
+        }
+        simpler
+    }
+
+    fn text(&self) -> String {
+        format!("{}-{} {}: {}", self.min, self.max, self.char, self.password)
+    }
+}
+
+#[test]
+fn test_validation_matches_naive_reference() {
+    assert_property(
+        "password validation matches the naive reference",
+        &FuzzConfig::from_env(),
+        Line::generate,
+        Line::shrink,
+        |line| {
+            let password: RecoveredPassword = line.text().parse().map_err(|e| format!("{}", e))?;
+            equal("min", i64::from(password.policy.min), line.min)?;
+            equal("max", i64::from(password.policy.max), line.max)?;
+            equal("char", password.policy.char, line.char)?;
+            equal(
+                "password",
+                password.password.as_str(),
+                line.password.as_str(),
+            )?;
+
+            let count = line.password.chars().filter(|&c| c == line.char).count() as i64;
+            equal(
+                "validate_part1",
+                password.validate_part1(),
+                line.min <= count && count <= line.max,
+            )?;
+            let at =
+                |position: i64| line.password.chars().nth(position as usize - 1) == Some(line.char);
+            equal(
+                "validate_part2",
+                password.validate_part2(),
+                at(line.min) != at(line.max),
+            )
+        },
+    );
+}
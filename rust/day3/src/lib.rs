@@ -0,0 +1,286 @@
+//! Day 3: Toboggan Trajectory. Ride a toboggan down a map that repeats itself to the right and
+//! count the trees on the way.
+
+use common::error::ParseError;
+use common::grid::Grid;
+use common::{trace, Found, Solution};
+use std::convert::TryFrom;
+use std::ops::{Index, IndexMut};
+
+/// A fixed-size buffer whose indexes wrap around, so `ring[len]` is the same as `ring[0]`.
+///
+/// Indexing an empty buffer panics. The map of [`Toboggan`] is a [`Grid`] now, which wraps
+/// around the same way in two dimensions, but this stays for the crates using it.
+#[derive(Clone, Debug)]
+pub struct RingBuffer<T> {
+    array: Vec<T>,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(array: Vec<T>) -> Self {
+        RingBuffer { array }
+    }
+
+    /// The number of elements before the buffer wraps around.
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+}
+
+impl RingBuffer<char> {
+    pub fn from_string(input: &str) -> Self {
+        let input_list: Vec<char> = input.chars().collect();
+        RingBuffer { array: input_list }
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.array[index % self.array.len()]
+    }
+}
+
+impl<T> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let max = self.array.len();
+        &mut self.array[index % max]
+    }
+}
+
+/// How far right the toboggan goes on each step.
+#[derive(Clone, Copy, Debug)]
+pub enum HorizontalMove {
+    Right(usize),
+}
+
+/// How far down the toboggan goes on each step.
+#[derive(Clone, Copy, Debug)]
+pub enum VerticalMove {
+    Down(usize),
+}
+
+/// A toboggan on the map. Every square it lands on is marked: `X` for a tree, `O` for open
+/// ground.
+#[derive(Clone, Debug)]
+pub struct Toboggan {
+    pos_x: usize,
+    pos_y: usize,
+    tree_count: usize,
+    map: Grid<char>,
+}
+
+impl Toboggan {
+    /// Rides a fresh copy of this toboggan down the whole map with the given slope and returns
+    /// how many trees it hit.
+    pub fn count_trees(&self, move_x: HorizontalMove, move_y: VerticalMove) -> usize {
+        let mut toboggan = self.clone();
+        toboggan.move_until_finished_to(&move_x, &move_y);
+        toboggan.tree_count
+    }
+
+    /// How many trees the toboggan has hit so far.
+    pub fn tree_count(&self) -> usize {
+        self.tree_count
+    }
+
+    /// The `(x, y)` position of the toboggan, with `x` not wrapped around.
+    pub fn position(&self) -> (usize, usize) {
+        (self.pos_x, self.pos_y)
+    }
+
+    /// Keeps moving with the same slope until the bottom of the map.
+    pub fn move_until_finished_to(&mut self, move_x: &HorizontalMove, move_y: &VerticalMove) {
+        for _ in 1..self.map.height() {
+            self.move_to(*move_x, *move_y);
+        }
+    }
+
+    /// Moves once. Moves that would leave the bottom of the map are ignored.
+    pub fn move_to(&mut self, move_x: HorizontalMove, move_y: VerticalMove) {
+        let (dest_y, dest_x) = self.parse_moves(move_x, move_y);
+        if dest_y >= self.map.height() {
+            return;
+        }
+        self.do_move(dest_y, dest_x);
+    }
+
+    fn parse_moves(&self, move_x: HorizontalMove, move_y: VerticalMove) -> (usize, usize) {
+        let dest_y = {
+            match move_y {
+                VerticalMove::Down(amount) => self.pos_y + amount,
+            }
+        };
+        let dest_x = {
+            match move_x {
+                HorizontalMove::Right(amount) => self.pos_x + amount,
+            }
+        };
+        (dest_y, dest_x)
+    }
+
+    fn do_move(&mut self, dest_y: usize, dest_x: usize) {
+        let arrival_change = self.process_arrival(dest_y, dest_x);
+        *self.map.get_wrapping_mut(dest_x, dest_y) = arrival_change;
+        self.pos_x = dest_x;
+        self.pos_y = dest_y;
+    }
+
+    fn process_arrival(&mut self, dest_y: usize, dest_x: usize) -> char {
+        let at_destination = *self.map.get_wrapping(dest_x, dest_y);
+        match at_destination {
+            '#' => {
+                self.tree_count += 1;
+                'X'
+            }
+            _ => 'O',
+        }
+    }
+}
+
+impl TryFrom<&[&str]> for Toboggan {
+    type Error = ParseError;
+
+    /// Builds the map from its lines, ignoring blank ones and the whitespace around rows, and
+    /// places the toboggan at the top left corner. Every row must be made of `.` and `#` and be
+    /// as wide as the first one.
+    fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
+        let map = Grid::from_rows(
+            input.iter().map(|line| line.trim()),
+            |c| Some(c).filter(|&c| c == '.' || c == '#'),
+            "`.` or `#`",
+        )
+        .map_err(|err| {
+            // The grid only saw the trimmed rows.
+            let line = input[err.location().line - 1];
+            let indent = line.len() - line.trim_start().len();
+            err.right(line[..indent].chars().count())
+        })?;
+        Ok(Toboggan {
+            pos_x: 0,
+            pos_y: 0,
+            tree_count: 0,
+            map,
+        })
+    }
+}
+
+/// The slopes checked in part 2.
+pub const SLOPES: [(HorizontalMove, VerticalMove); 5] = [
+    (HorizontalMove::Right(1), VerticalMove::Down(1)),
+    (HorizontalMove::Right(3), VerticalMove::Down(1)),
+    (HorizontalMove::Right(5), VerticalMove::Down(1)),
+    (HorizontalMove::Right(7), VerticalMove::Down(1)),
+    (HorizontalMove::Right(1), VerticalMove::Down(2)),
+];
+
+impl Solution for Toboggan {
+    type Parsed<'a> = Toboggan;
+    /// Part 2 is `none` when the product doesn't fit in 128 bits.
+    type Answer = Found<u128>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let rows: Vec<&str> = input.lines().collect();
+        trace::count("records", rows.len() as u64);
+        Toboggan::try_from(&rows[..])
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        let trees = parsed.count_trees(HorizontalMove::Right(3), VerticalMove::Down(1));
+        Found(Some(trees as u128))
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        Found(
+            SLOPES
+                .iter()
+                .map(|&(move_x, move_y)| parsed.count_trees(move_x, move_y))
+                .filter(|&tree_count| tree_count != 0)
+                .try_fold(1u128, |product, tree_count| {
+                    product.checked_mul(tree_count as u128)
+                }),
+        )
+    }
+}
+
+#[test]
+fn test_toboggan_single_slope() {
+    let slope_test_input = include_str!("../examples/example.txt");
+
+    let mut toboggan =
+        Toboggan::try_from(&slope_test_input.lines().collect::<Vec<&str>>()[..]).unwrap();
+    toboggan.move_until_finished_to(&HorizontalMove::Right(3), &VerticalMove::Down(1));
+    assert_eq!(toboggan.tree_count, 7);
+}
+
+#[test]
+fn test_toboggan_multi_slope() {
+    let slope_test_input = include_str!("../examples/example.txt");
+    let input_vec: Vec<&str> = slope_test_input.lines().collect();
+    let mut total_tree_count = 0;
+
+    let moves: Vec<(HorizontalMove, VerticalMove, usize)> = vec![
+        (HorizontalMove::Right(1), VerticalMove::Down(1), 2),
+        (HorizontalMove::Right(3), VerticalMove::Down(1), 7),
+        (HorizontalMove::Right(5), VerticalMove::Down(1), 3),
+        (HorizontalMove::Right(7), VerticalMove::Down(1), 4),
+        (HorizontalMove::Right(1), VerticalMove::Down(2), 2),
+    ];
+
+    moves
+        .into_iter()
+        .for_each(|(move_x, move_y, expected_tree_count)| {
+            let mut toboggan = Toboggan::try_from(&input_vec[..]).unwrap();
+            toboggan.move_until_finished_to(&move_x, &move_y);
+            total_tree_count += toboggan.tree_count;
+            assert_eq!(
+                toboggan.tree_count, expected_tree_count,
+                "failed at move {:?} {:?}",
+                move_x, move_y
+            );
+        });
+}
+
+#[test]
+fn test_ring_buffer() {
+    let ring = RingBuffer::from_string("foo");
+    assert_eq!(ring[0], 'f');
+    assert_eq!(ring[3], 'f');
+}
+
+#[test]
+fn test_toboggan_parse_errors() {
+    use common::error::Location;
+
+    assert_eq!(
+        Toboggan::parse("..#\n.x.").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(2, 2),
+            text: "x".to_string(),
+            expected: "`.` or `#`",
+        })
+    );
+    assert_eq!(
+        Toboggan::parse("..#\n\n  .#").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(3, 3),
+            text: ".#".to_string(),
+            expected: "a row as wide as the first one",
+        })
+    );
+}
+
+#[test]
+fn test_tree_product_does_not_overflow() {
+    // Every slope hits a tree on every row of a map of trees.
+    let rows = vec!["#"; 200_000];
+    let toboggan = Toboggan::try_from(&rows[..]).unwrap();
+    let product = 199_999u128.pow(4) * 99_999;
+    assert!(product > u64::MAX as u128);
+    assert_eq!(Toboggan::part2(&toboggan), Found(Some(product)));
+}
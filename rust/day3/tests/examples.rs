@@ -0,0 +1,7 @@
+use day3::Toboggan;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<Toboggan>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}
@@ -0,0 +1,17 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use common::Solution;
+use day3::Toboggan;
+use std::convert::TryFrom;
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("Toboggan::try_from", &config, &corpus, |input| {
+        let _ = Toboggan::try_from(&input.lines().collect::<Vec<_>>()[..]);
+    });
+    assert_no_panics("Toboggan::parse", &config, &corpus, |input| {
+        let _ = Toboggan::parse(input);
+    });
+}
@@ -0,0 +1,92 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_number, shrink_string, shrink_vec};
+use common::random::Rng;
+use day3::{HorizontalMove, Toboggan, VerticalMove};
+use std::convert::TryFrom;
+
+/// A map and the slope to ride it with.
+#[derive(Clone, Debug)]
+struct Ride {
+    rows: Vec<String>,
+    right: i64,
+    down: i64,
+}
+
+impl Ride {
+    fn generate(rng: &mut Rng) -> Self {
+        let width = rng.range(1, 10) as usize;
+        let trees = rng.below(5);
+        let rows = (0..rng.range(1, 12))
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.chance(trees, 5) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        Ride {
+            rows,
+            right: rng.range(0, 7),
+            down: rng.range(1, 3),
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut simpler: Vec<Ride> = shrink_vec(&self.rows, |row| {
+            // Turning trees into open ground keeps every row as wide.
+            shrink_string(row, '.')
+                .into_iter()
+                .filter(|smaller| smaller.len() == row.len())
+                .collect()
+        })
+        .into_iter()
+        .filter(|rows| !rows.is_empty())
+        .map(|rows| Ride {
+            rows,
+            ..self.clone()
+        })
+        .collect();
+        for right in shrink_number(self.right, 0) {
+            simpler.push(Ride {
+                right,
+                ..self.clone()
+            });
+        }
+        for down in shrink_number(self.down, 1) {
+            simpler.push(Ride {
+                down,
+                ..self.clone()
+            });
+        }
+        simpler
+    }
+
+    /// Counts the trees on `(k * right, k * down)` for every step `k`, wrapping around the
+    /// width of the map.
+    fn naive_trees(&self) -> usize {
+        let width = self.rows[0].len();
+        (1..)
+            .map(|step| (step * self.right as usize, step * self.down as usize))
+            .take_while(|&(_, y)| y < self.rows.len())
+            .filter(|&(x, y)| self.rows[y].as_bytes()[x % width] == b'#')
+            .count()
+    }
+}
+
+#[test]
+fn test_tree_count_matches_naive_reference() {
+    assert_property(
+        "tree count matches the naive reference",
+        &FuzzConfig::from_env(),
+        Ride::generate,
+        Ride::shrink,
+        |ride| {
+            let rows: Vec<&str> = ride.rows.iter().map(String::as_str).collect();
+            let toboggan = Toboggan::try_from(&rows[..]).map_err(|e| e.to_string())?;
+            let trees = toboggan.count_trees(
+                HorizontalMove::Right(ride.right as usize),
+                VerticalMove::Down(ride.down as usize),
+            );
+            equal("count_trees", trees, ride.naive_trees())
+        },
+    );
+}
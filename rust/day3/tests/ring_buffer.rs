@@ -0,0 +1,27 @@
+use day3::{HorizontalMove, RingBuffer, Toboggan, VerticalMove};
+use std::convert::TryFrom;
+
+#[test]
+fn test_ring_buffer_wraps_any_type() {
+    let mut ring = RingBuffer::new(vec![1, 2, 3]);
+    assert_eq!(ring.len(), 3);
+    assert_eq!(ring[4], 2);
+
+    ring[5] = 30;
+    assert_eq!(ring[2], 30);
+}
+
+#[test]
+fn test_toboggan_moves() {
+    let mut toboggan = Toboggan::try_from(&["..#", "#..", ".#."][..]).unwrap();
+    toboggan.move_to(HorizontalMove::Right(1), VerticalMove::Down(1));
+    assert_eq!(toboggan.position(), (1, 1));
+    assert_eq!(toboggan.tree_count(), 0);
+
+    toboggan.move_to(HorizontalMove::Right(3), VerticalMove::Down(1));
+    assert_eq!(toboggan.position(), (4, 2));
+    assert_eq!(toboggan.tree_count(), 1);
+
+    toboggan.move_to(HorizontalMove::Right(1), VerticalMove::Down(1));
+    assert_eq!(toboggan.position(), (4, 2));
+}
@@ -0,0 +1,349 @@
+//! Day 4: Passport Processing. Check a batch of passports for required fields and, in part 2,
+//! for valid field values.
+
+use common::error::{Location, ParseError};
+use common::records::records;
+use common::Solution;
+use std::convert::TryFrom;
+use verex::{Expression as E, Verex};
+
+/// The `key:value` pairs of a single passport, separated by spaces or newlines.
+pub type CredentialInput<'a> = &'a str;
+
+/// The name of a checked field and the function validating its value.
+pub type FieldValidation = (&'static str, fn(&Credentials<'_>) -> bool);
+
+/// The fields of a passport that are checked, borrowed from its input. Missing fields are left
+/// empty. Nothing is checked until a validation function is called, so a passport with a year
+/// that isn't a number is still a passport, just not a valid one.
+pub struct Credentials<'a> {
+    pub byr: &'a str,
+    pub iyr: &'a str,
+    pub eyr: &'a str,
+    pub hgt: &'a str,
+    pub hcl: &'a str,
+    pub ecl: &'a str,
+    pub pid: &'a str,
+}
+
+impl Credentials<'_> {
+    /// One validation function per checked field, with the name of the field.
+    pub const FIELD_VALIDATION_FUNCS: [FieldValidation; 7] = [
+        ("byr", |c| c.valid_byr()),
+        ("hgt", |c| c.valid_hgt()),
+        ("ecl", |c| c.valid_ecl()),
+        ("eyr", |c| c.valid_eyr()),
+        ("hcl", |c| c.valid_hcl()),
+        ("iyr", |c| c.valid_iyr()),
+        ("pid", |c| c.valid_pid()),
+    ];
+
+    /// Whether every field passes its validation function.
+    pub fn has_valid_fields(&self) -> bool {
+        Self::FIELD_VALIDATION_FUNCS.iter().all(|(_, f)| f(self))
+    }
+
+    /// The fields that fail their validation function, in the order they are checked.
+    pub fn invalid_fields(&self) -> Vec<&'static str> {
+        Self::FIELD_VALIDATION_FUNCS
+            .iter()
+            .filter(|(_, f)| !f(self))
+            .map(|&(field, _)| field)
+            .collect()
+    }
+
+    /// Birth year: four digits, at least 1920 and at most 2002.
+    pub fn valid_byr(&self) -> bool {
+        valid_year(self.byr, 1920, 2002)
+    }
+
+    /// Issue year: four digits, at least 2010 and at most 2020.
+    pub fn valid_iyr(&self) -> bool {
+        valid_year(self.iyr, 2010, 2020)
+    }
+
+    /// Expiration year: four digits, at least 2020 and at most 2030.
+    pub fn valid_eyr(&self) -> bool {
+        valid_year(self.eyr, 2020, 2030)
+    }
+
+    /// Height: a number followed by `cm` (150 to 193) or `in` (59 to 76).
+    pub fn valid_hgt(&self) -> bool {
+        let height_regex = Verex::new()
+            .capture_expr(E::String("^(\\d+)(cm|in)$"))
+            .compile()
+            .unwrap();
+        let captures = match height_regex.captures(self.hgt) {
+            None => return false,
+            Some(captures) => captures,
+        };
+        let height = captures.at(2).unwrap().parse().unwrap_or(0);
+
+        match captures.at(3).unwrap() {
+            "cm" => (150..=193).contains(&height),
+            "in" => (59..=76).contains(&height),
+            _ => false,
+        }
+    }
+
+    /// Hair color: a `#` followed by exactly six characters 0-9 or a-f.
+    pub fn valid_hcl(&self) -> bool {
+        let hcl_regex = Verex::new()
+            .capture_expr(E::String("^#[a-f0-9]{6}$"))
+            .compile()
+            .unwrap();
+        hcl_regex.captures(self.hcl).is_some()
+    }
+
+    /// Eye color: exactly one of `amb` `blu` `brn` `gry` `grn` `hzl` `oth`.
+    pub fn valid_ecl(&self) -> bool {
+        ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&self.ecl)
+    }
+
+    /// Passport ID: a nine-digit number, including leading zeroes.
+    pub fn valid_pid(&self) -> bool {
+        let pid_regex = Verex::new()
+            .capture_expr(E::String("^\\d{9}$"))
+            .compile()
+            .unwrap();
+        pid_regex.captures(self.pid).is_some()
+    }
+}
+
+/// Whether `year` is four digits, at least `low` and at most `high`.
+fn valid_year(year: &str, low: u32, high: u32) -> bool {
+    year.len() == 4
+        && year.bytes().all(|b| b.is_ascii_digit())
+        && year.parse().is_ok_and(|year| (low..=high).contains(&year))
+}
+
+impl<'a> TryFrom<CredentialInput<'a>> for Credentials<'a> {
+    type Error = ParseError;
+
+    /// Parses the `key:value` pairs of a single passport. Unknown keys are ignored.
+    fn try_from(input: CredentialInput<'a>) -> Result<Self, Self::Error> {
+        let mut credentials = Self {
+            byr: "",
+            iyr: "",
+            eyr: "",
+            hgt: "",
+            hcl: "",
+            ecl: "",
+            pid: "",
+        };
+        for (number, line) in input.lines().enumerate() {
+            for key_value in line.split_whitespace() {
+                let (key, value) =
+                    key_value
+                        .split_once(':')
+                        .ok_or_else(|| ParseError::Unexpected {
+                            at: Location::of(number + 1, line, key_value),
+                            text: key_value.to_string(),
+                            expected: "a `key:value` field",
+                        })?;
+                match key {
+                    "byr" => credentials.byr = value,
+                    "iyr" => credentials.iyr = value,
+                    "eyr" => credentials.eyr = value,
+                    "hgt" => credentials.hgt = value,
+                    "hcl" => credentials.hcl = value,
+                    "ecl" => credentials.ecl = value,
+                    "pid" => credentials.pid = value,
+                    _ => {}
+                }
+            }
+        }
+        Ok(credentials)
+    }
+}
+
+/// A single passport from the batch: the keys it has and its parsed fields, both borrowed from
+/// the batch.
+pub struct CredentialsValidator<'a> {
+    keys: Vec<&'a str>,
+    credentials: Credentials<'a>,
+}
+
+impl<'a> CredentialsValidator<'a> {
+    /// Every field but `cid`, which is allowed to be missing.
+    pub const REQUIRED_FIELDS: [&'static str; 7] =
+        ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+    /// Whether the passport has every required field and all of them hold valid values.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_credential() && self.credentials.has_valid_fields()
+    }
+
+    /// Whether the passport has every required field.
+    pub fn is_valid_credential(&self) -> bool {
+        self.missing_fields().is_empty()
+    }
+
+    /// The required fields the passport doesn't have.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        Self::REQUIRED_FIELDS
+            .iter()
+            .filter(|field| !self.keys.contains(field))
+            .copied()
+            .collect()
+    }
+
+    pub fn credentials(&self) -> &Credentials<'a> {
+        &self.credentials
+    }
+
+    /// Runs [`CredentialsValidator::is_valid`] on every passport of the batch.
+    pub fn validate_batch(input: &str) -> Result<Vec<bool>, ParseError> {
+        Ok(CredentialsValidator::parse_batch(input)?
+            .iter()
+            .map(CredentialsValidator::is_valid)
+            .collect())
+    }
+
+    /// Splits a batch into passports and parses them. Passports are separated by blank lines.
+    pub fn parse_batch(input: &'a str) -> Result<Vec<Self>, ParseError> {
+        records(input)
+            .map(|record| Self::try_from(record.text()).map_err(|e| e.below(record.first_line - 1)))
+            .collect()
+    }
+}
+
+impl<'a> TryFrom<CredentialInput<'a>> for CredentialsValidator<'a> {
+    type Error = ParseError;
+
+    fn try_from(input: CredentialInput<'a>) -> Result<Self, Self::Error> {
+        let credentials = Credentials::try_from(input)?;
+        // Parsing the credentials checked that every field is a `key:value` pair.
+        let keys = input
+            .split_whitespace()
+            .filter_map(|field| field.split_once(':'))
+            .map(|(key, _)| key)
+            .collect();
+        Ok(Self { keys, credentials })
+    }
+}
+
+impl<'x> Solution for CredentialsValidator<'x> {
+    type Parsed<'a> = Vec<CredentialsValidator<'a>>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        CredentialsValidator::parse_batch(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.iter().filter(|v| v.is_valid_credential()).count()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed.iter().filter(|v| v.is_valid()).count()
+    }
+}
+
+#[test]
+fn test_credentials_validation() {
+    let input = include_str!("../examples/example.txt");
+
+    let expected_result = [true, false, true, false];
+
+    assert_eq!(
+        CredentialsValidator::validate_batch(input),
+        Ok(expected_result.to_vec())
+    );
+}
+
+#[test]
+fn test_credential_parser() {
+    let input: CredentialInput =
+        "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884 hcl:#cfa07d byr:1929";
+    let creds = Credentials::try_from(input).unwrap();
+
+    assert_eq!(creds.iyr, "2013")
+}
+
+#[test]
+fn test_credential_parse_errors() {
+    assert_eq!(
+        CredentialsValidator::parse("byr:1980\n\niyr:2012\npid:1 eyr").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(4, 7),
+            text: "eyr".to_string(),
+            expected: "a `key:value` field",
+        })
+    );
+}
+
+#[test]
+fn test_years_that_are_not_numbers() {
+    let passport = CredentialsValidator::try_from(
+        "byr:abcd iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704",
+    )
+    .unwrap();
+    assert!(passport.is_valid_credential());
+    assert!(!passport.is_valid());
+    assert_eq!(passport.credentials().invalid_fields(), vec!["byr"]);
+
+    assert_eq!(
+        CredentialsValidator::validate_batch("byr:19x0\n\nbyr:1980"),
+        Ok(vec![false, false])
+    );
+}
+
+#[test]
+fn test_field_validation() {
+    let mut cred = Credentials {
+        byr: "",
+        iyr: "",
+        eyr: "",
+        hgt: "",
+        hcl: "",
+        ecl: "",
+        pid: "",
+    };
+
+    assert!(!cred.valid_byr());
+    cred.byr = "2002";
+    assert!(cred.valid_byr());
+    for year in &["02002", "+200", "200", "2003", "20O2"] {
+        cred.byr = year;
+        assert!(!cred.valid_byr(), "{}", year);
+    }
+    cred.byr = "2002";
+
+    assert!(!cred.valid_hgt());
+    cred.hgt = "190cm";
+    assert!(cred.valid_hgt());
+    cred.hgt = "60in";
+    assert!(cred.valid_hgt());
+    cred.hgt = "60inx";
+    assert!(!cred.valid_hgt());
+
+    cred.hcl = "#12345f";
+    assert!(cred.valid_hcl());
+    cred.hcl = "#12345|";
+    assert!(!cred.valid_hcl());
+
+    assert_eq!(
+        cred.invalid_fields(),
+        vec!["hgt", "ecl", "eyr", "hcl", "iyr", "pid"]
+    );
+}
+
+#[test]
+fn test_missing_fields_are_keys() {
+    let passport = CredentialsValidator::try_from("hcl:#pidbyr ecl:iyr\nhgt:eyr cid:1").unwrap();
+    assert_eq!(passport.missing_fields(), vec!["byr", "iyr", "eyr", "pid"]);
+}
+
+#[test]
+fn test_full_passport_validation() {
+    let invalid_input = include_str!("../examples/invalid_passports.txt");
+
+    let results = CredentialsValidator::validate_batch(invalid_input).unwrap();
+    assert!(results.iter().all(|&r| !r));
+
+    let valid_input = include_str!("../examples/valid_passports.txt");
+
+    let results = CredentialsValidator::validate_batch(valid_input).unwrap();
+    assert!(results.iter().all(|&r| r))
+}
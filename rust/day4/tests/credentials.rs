@@ -0,0 +1,33 @@
+use day4::{Credentials, CredentialsValidator};
+use std::convert::TryFrom;
+
+#[test]
+fn test_field_validation_funcs() {
+    let creds = Credentials::try_from(
+        "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+    )
+    .unwrap();
+    assert!(Credentials::FIELD_VALIDATION_FUNCS
+        .iter()
+        .all(|(_, validate)| validate(&creds)));
+    assert!(creds.invalid_fields().is_empty());
+    assert!(creds.has_valid_fields());
+
+    let creds = Credentials::try_from("pid:0123456789 byr:1980").unwrap();
+    assert!(!creds.valid_pid());
+    assert!(creds.valid_byr());
+}
+
+#[test]
+fn test_validator() {
+    let missing_hgt = CredentialsValidator::try_from(
+        "pid:087499704 ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+    )
+    .unwrap();
+    assert!(!missing_hgt.is_valid_credential());
+    assert_eq!(missing_hgt.missing_fields(), vec!["hgt"]);
+    assert!(!missing_hgt.is_valid());
+
+    let batch = CredentialsValidator::parse_batch("byr:1980\niyr:2012\n\npid:087499704").unwrap();
+    assert_eq!(batch.len(), 2);
+}
@@ -0,0 +1,9 @@
+use day4::CredentialsValidator;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<CredentialsValidator>(Path::new(env!(
+        "CARGO_MANIFEST_DIR"
+    )));
+}
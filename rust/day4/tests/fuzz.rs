@@ -0,0 +1,22 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use common::Solution;
+use day4::{Credentials, CredentialsValidator};
+use std::convert::TryFrom;
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("Credentials::try_from", &config, &corpus, |input| {
+        if let Ok(credentials) = Credentials::try_from(input) {
+            credentials.has_valid_fields();
+        }
+    });
+    assert_no_panics("CredentialsValidator::parse", &config, &corpus, |input| {
+        if let Ok(batch) = CredentialsValidator::parse(input) {
+            CredentialsValidator::part1(&batch);
+            CredentialsValidator::part2(&batch);
+        }
+    });
+}
@@ -0,0 +1,145 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_vec};
+use common::random::Rng;
+use day4::CredentialsValidator;
+use std::convert::TryFrom;
+
+/// A passport as its `(key, value)` fields, some of them missing and some of them invalid.
+type Passport = Vec<(&'static str, String)>;
+
+/// A passport mostly made of valid values, so that whole valid passports come up often, with
+/// invalid values of every kind mixed in.
+fn passport(rng: &mut Rng) -> Passport {
+    let mut fields = Passport::new();
+    let mut add = |rng: &mut Rng, key, value: &dyn Fn(&mut Rng, bool) -> String| {
+        if rng.chance(15, 16) {
+            let valid = rng.chance(5, 6);
+            let value = value(rng, valid);
+            fields.push((key, value));
+        }
+    };
+    let year = |low, high| {
+        move |rng: &mut Rng, valid| match (valid, rng.chance(1, 2)) {
+            (true, _) => rng.range(low, high).to_string(),
+            (false, true) => rng.range(low - 30, high + 30).to_string(),
+            (false, false) => {
+                let junk = *rng.pick(&["0", "x", "+", ""]);
+                format!("{}{}", junk, rng.range(low, high))
+            }
+        }
+    };
+    add(rng, "byr", &year(1920, 2002));
+    add(rng, "iyr", &year(2010, 2020));
+    add(rng, "eyr", &year(2020, 2030));
+    add(rng, "hgt", &|rng, valid| match (valid, rng.chance(1, 2)) {
+        (true, true) => format!("{}cm", rng.range(150, 193)),
+        (true, false) => format!("{}in", rng.range(59, 76)),
+        (false, _) => {
+            let unit = *rng.pick(&["cm", "in", "", "mm"]);
+            let junk = *rng.pick(&["", "x", "cm"]);
+            format!("{}{}{}", rng.range(40, 200), unit, junk)
+        }
+    });
+    add(rng, "hcl", &|rng, valid| {
+        let hex: Vec<char> = "0123456789abcdef".chars().collect();
+        let mut color: Vec<char> = (0..6).map(|_| *rng.pick(&hex)).collect();
+        if !valid {
+            match rng.below(3) {
+                0 => color[rng.below(6)] = *rng.pick(&['g', 'z', 'A', '|', '#']),
+                1 => color.truncate(rng.below(6)),
+                _ => color.push(*rng.pick(&hex)),
+            }
+        }
+        let prefix = if valid || rng.chance(1, 2) { "#" } else { "" };
+        prefix.to_string() + &color.into_iter().collect::<String>()
+    });
+    add(rng, "ecl", &|rng, valid| {
+        let colors: &[&str] = match valid {
+            true => &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"],
+            false => &["xyz", "gr", "bluu", "amber"],
+        };
+        rng.pick(colors).to_string()
+    });
+    add(rng, "pid", &|rng, valid| {
+        let length = if valid { 9 } else { *rng.pick(&[0, 8, 10]) };
+        (0..length)
+            .map(|_| *rng.pick(&['0', '1', '5', '9']))
+            .collect()
+    });
+    add(rng, "cid", &|rng, _| rng.range(1, 999).to_string());
+    rng.shuffle(&mut fields);
+    fields
+}
+
+fn text(passport: &Passport) -> String {
+    let fields: Vec<String> = passport
+        .iter()
+        .map(|(key, value)| format!("{}:{}", key, value))
+        .collect();
+    // Spread the fields over a few lines, like the batches do.
+    fields
+        .chunks(3)
+        .map(|line| line.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The rules of part 2, written down as plainly as possible.
+fn naive_valid_value(key: &str, value: &str) -> bool {
+    let year =
+        |low, high| value.len() == 4 && value.parse().is_ok_and(|y: u32| (low..=high).contains(&y));
+    let number = |text: &str, low, high| {
+        !text.is_empty()
+            && text.bytes().all(|b| b.is_ascii_digit())
+            && text.parse().is_ok_and(|n: u32| (low..=high).contains(&n))
+    };
+    match key {
+        "byr" => year(1920, 2002),
+        "iyr" => year(2010, 2020),
+        "eyr" => year(2020, 2030),
+        "hgt" => match (value.strip_suffix("cm"), value.strip_suffix("in")) {
+            (Some(cm), _) => number(cm, 150, 193),
+            (_, Some(inches)) => number(inches, 59, 76),
+            _ => false,
+        },
+        "hcl" => {
+            value.len() == 7
+                && value.starts_with('#')
+                && value[1..]
+                    .bytes()
+                    .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        }
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => value.len() == 9 && value.bytes().all(|b| b.is_ascii_digit()),
+        _ => true,
+    }
+}
+
+#[test]
+fn test_validation_matches_naive_reference() {
+    assert_property(
+        "passport validation matches the naive reference",
+        &FuzzConfig::from_env(),
+        passport,
+        |fields| shrink_vec(fields, |_| vec![]),
+        |fields| {
+            let text = text(fields);
+            let validator =
+                CredentialsValidator::try_from(text.as_str()).map_err(|e| e.to_string())?;
+            let has = |key: &str| fields.iter().any(|&(k, _)| k == key);
+            let complete = CredentialsValidator::REQUIRED_FIELDS
+                .iter()
+                .all(|key| has(key));
+            equal(
+                "is_valid_credential",
+                validator.is_valid_credential(),
+                complete,
+            )?;
+            let valid = complete
+                && fields
+                    .iter()
+                    .all(|(key, value)| naive_valid_value(key, value));
+            equal("is_valid", validator.is_valid(), valid)
+        },
+    );
+}
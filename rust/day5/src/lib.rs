@@ -0,0 +1,259 @@
+//! Day 5: Binary Boarding. Decode boarding passes into seat IDs and find the one free seat on
+//! the plane.
+
+use common::error::{parse_lines, Location, ParseError};
+use common::{Found, Solution};
+use std::str::FromStr;
+
+/// Finds the seat missing from `seat_ids` whose neighbours (the IDs right before and after it)
+/// are both taken.
+pub fn find_free_seat(seat_ids: &[i32]) -> Option<i32> {
+    for &seat_id in seat_ids {
+        let next_id_empty = seat_ids.iter().all(|&id| id != seat_id + 1);
+        let next_next_busy = seat_ids.iter().any(|&id| id == seat_id + 2);
+        if next_id_empty && next_next_busy {
+            return Some(seat_id + 1);
+        }
+    }
+    None
+}
+
+/// Decodes a boarding pass straight into its seat ID, without building a [`SeatCode`]. A pass
+/// is its seat ID written in binary, with `B` and `R` as ones and `F` and `L` as zeroes. The
+/// pass isn't checked.
+pub fn decode_seat_id(code: &str) -> i32 {
+    code.trim()
+        .bytes()
+        .fold(0, |id, c| (id << 1) | i32::from(c == b'B' || c == b'R'))
+}
+
+/// A boarding pass like `FBFBBFFRLR`: seven `F`/`B` characters for the row and three `L`/`R`
+/// characters for the column.
+pub struct SeatCode {
+    row_code: Vec<char>,
+    column_code: Vec<char>,
+}
+
+impl SeatCode {
+    pub const MAX_ROW: i32 = 127;
+    pub const MAX_COLUMN: i32 = 7;
+
+    /// The row of the seat, from 0 to [`SeatCode::MAX_ROW`].
+    pub fn find_row(&self) -> i32 {
+        self.binary_space_partition(Self::MAX_ROW, 'F', 'B', &self.row_code)
+    }
+
+    /// The column of the seat, from 0 to [`SeatCode::MAX_COLUMN`].
+    pub fn find_column(&self) -> i32 {
+        self.binary_space_partition(Self::MAX_COLUMN, 'L', 'R', &self.column_code)
+    }
+
+    /// The unique ID of the seat: its row times 8 plus its column.
+    pub fn seat_id(&self) -> i32 {
+        (self.find_row() * 8) + self.find_column()
+    }
+
+    /// How the possible rows narrow down: the character read and the lowest and highest rows
+    /// left after it, one step per character of the row code.
+    pub fn row_steps(&self) -> Vec<(char, i32, i32)> {
+        Self::partition_steps(Self::MAX_ROW, 'F', 'B', &self.row_code)
+    }
+
+    /// Same as [`SeatCode::row_steps`], for the columns.
+    pub fn column_steps(&self) -> Vec<(char, i32, i32)> {
+        Self::partition_steps(Self::MAX_COLUMN, 'L', 'R', &self.column_code)
+    }
+
+    fn binary_space_partition(
+        &self,
+        max: i32,
+        low_half_code: char,
+        upper_half_code: char,
+        input: &[char],
+    ) -> i32 {
+        match Self::partition_steps(max, low_half_code, upper_half_code, input).last() {
+            Some(&(last_read, lower_bound, _)) if last_read == low_half_code => lower_bound,
+            Some(&(last_read, _, upper_bound)) if last_read == upper_half_code => upper_bound,
+            _ => 0,
+        }
+    }
+
+    fn partition_steps(
+        max: i32,
+        low_half_code: char,
+        upper_half_code: char,
+        input: &[char],
+    ) -> Vec<(char, i32, i32)> {
+        let mut upper_bound = max;
+        let mut lower_bound = 0;
+        let mut steps = Vec::with_capacity(input.len());
+        for &c in input {
+            if c == low_half_code {
+                upper_bound -= (((upper_bound - lower_bound) as f64) / 2.0).ceil() as i32
+            } else if c == upper_half_code {
+                lower_bound += (((upper_bound - lower_bound) as f64) / 2.0).ceil() as i32
+            }
+            steps.push((c, lower_bound, upper_bound));
+        }
+        steps
+    }
+}
+
+impl FromStr for SeatCode {
+    type Err = ParseError;
+
+    /// Parses a boarding pass, ignoring the whitespace around it.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let code = input.trim();
+        let mut chars = code.char_indices();
+        let mut next = |halves: &str, expected| match chars.next() {
+            Some((_, c)) if halves.contains(c) => Ok(c),
+            Some((index, c)) => Err(ParseError::Unexpected {
+                at: Location::of(1, input, &code[index..]),
+                text: c.to_string(),
+                expected,
+            }),
+            None => Err(ParseError::Missing {
+                at: Location::of(1, input, &code[code.len()..]),
+                expected,
+            }),
+        };
+        let row_code = (0..7)
+            .map(|_| next("FB", "`F` or `B`"))
+            .collect::<Result<_, _>>()?;
+        let column_code = (0..3)
+            .map(|_| next("LR", "`L` or `R`"))
+            .collect::<Result<_, _>>()?;
+        if let Some((index, _)) = chars.next() {
+            return Err(ParseError::Unexpected {
+                at: Location::of(1, input, &code[index..]),
+                text: code[index..].to_string(),
+                expected: "the end of the boarding pass",
+            });
+        }
+        Ok(Self {
+            row_code,
+            column_code,
+        })
+    }
+}
+
+impl Solution for SeatCode {
+    type Parsed<'a> = Vec<SeatCode>;
+    /// There is no highest seat ID without boarding passes, and no free seat without a gap.
+    type Answer = Found<i32>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        Found(parsed.iter().map(SeatCode::seat_id).max())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        let seat_ids: Vec<i32> = parsed.iter().map(SeatCode::seat_id).collect();
+        Found(find_free_seat(&seat_ids))
+    }
+}
+
+#[test]
+fn test_basic_seat_code() {
+    let code = String::from("BFFFBBFRRR");
+    let seat: SeatCode = code.parse().unwrap();
+
+    assert_eq!(seat.row_code, "BFFFBBF".chars().collect::<Vec<char>>());
+    assert_eq!(seat.column_code, "RRR".chars().collect::<Vec<char>>());
+
+    assert_eq!(seat.find_row(), 70);
+    assert_eq!(seat.find_column(), 7);
+    assert_eq!(seat.seat_id(), 567);
+}
+
+#[test]
+fn test_seat_find_row() {
+    let seat: SeatCode = "FFFBBBFRRR".parse().unwrap();
+    assert_eq!(seat.find_row(), 14);
+    assert_eq!(seat.find_column(), 7);
+    assert_eq!(seat.seat_id(), 119);
+
+    let seat: SeatCode = "BBFFBBFRLL".parse().unwrap();
+    assert_eq!(seat.find_row(), 102);
+    assert_eq!(seat.find_column(), 4);
+    assert_eq!(seat.seat_id(), 820);
+}
+
+#[test]
+fn test_no_answer() {
+    assert_eq!(SeatCode::part1(&vec![]), Found(None));
+    assert_eq!(SeatCode::part2(&vec![]), Found(None));
+    let seats = SeatCode::parse("FFFBBBFRRR\nBBFFBBFRLL").unwrap();
+    assert_eq!(SeatCode::part1(&seats), Found(Some(820)));
+    assert_eq!(SeatCode::part2(&seats).to_string(), "none");
+}
+
+#[test]
+fn test_partition_steps() {
+    let seat: SeatCode = "FBFBBFFRLR".parse().unwrap();
+    assert_eq!(
+        seat.row_steps(),
+        vec![
+            ('F', 0, 63),
+            ('B', 32, 63),
+            ('F', 32, 47),
+            ('B', 40, 47),
+            ('B', 44, 47),
+            ('F', 44, 45),
+            ('F', 44, 44),
+        ]
+    );
+    assert_eq!(
+        seat.column_steps(),
+        vec![('R', 4, 7), ('L', 4, 5), ('R', 5, 5)]
+    );
+}
+
+#[test]
+fn test_seat_code_parse_errors() {
+    assert_eq!(
+        SeatCode::parse("BFFFBBFRRR\n FBFBBFFRXR").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(2, 10),
+            text: "X".to_string(),
+            expected: "`L` or `R`",
+        })
+    );
+    assert_eq!(
+        "FBFBB".parse::<SeatCode>().err(),
+        Some(ParseError::Missing {
+            at: Location::new(1, 6),
+            expected: "`F` or `B`",
+        })
+    );
+    assert_eq!(
+        "FBFBBFFRLRLL".parse::<SeatCode>().err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(1, 11),
+            text: "LL".to_string(),
+            expected: "the end of the boarding pass",
+        })
+    );
+}
+
+#[test]
+fn test_decode_seat_id_matches_seat_code() {
+    for id in 0..1024 {
+        let code: String = (0..10)
+            .rev()
+            .map(|bit| match (bit >= 3, id >> bit & 1 == 1) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            })
+            .collect();
+        let seat: SeatCode = code.parse().unwrap();
+        assert_eq!(seat.seat_id(), id, "{}", code);
+        assert_eq!(decode_seat_id(&code), id, "{}", code);
+    }
+}
@@ -0,0 +1,7 @@
+use day5::SeatCode;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<SeatCode>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}
@@ -0,0 +1,21 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use common::Solution;
+use day5::SeatCode;
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("SeatCode::from_str", &config, &corpus, |input| {
+        if let Ok(seat) = input.parse::<SeatCode>() {
+            seat.seat_id();
+        }
+    });
+    assert_no_panics("SeatCode", &config, &corpus, |input| {
+        if let Ok(seats) = SeatCode::parse(input) {
+            let _ = SeatCode::part1(&seats);
+            let _ = SeatCode::part2(&seats);
+        }
+    });
+}
@@ -0,0 +1,48 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_number};
+use day5::{decode_seat_id, SeatCode};
+
+/// The boarding pass of the seat at `row` and `column`: both in binary, with `B` and `R` as
+/// ones.
+fn encode(row: i64, column: i64) -> String {
+    let bits = |value: i64, width: usize, zero: char, one: char| -> String {
+        (0..width)
+            .rev()
+            .map(|bit| if value >> bit & 1 == 1 { one } else { zero })
+            .collect()
+    };
+    bits(row, 7, 'F', 'B') + &bits(column, 3, 'L', 'R')
+}
+
+#[test]
+fn test_decoding_roundtrips() {
+    assert_property(
+        "decoding a boarding pass gives back its seat",
+        &FuzzConfig::from_env(),
+        |rng| {
+            (
+                rng.range(0, SeatCode::MAX_ROW.into()),
+                rng.range(0, SeatCode::MAX_COLUMN.into()),
+            )
+        },
+        |&(row, column)| {
+            let rows = shrink_number(row, 0).into_iter().map(|row| (row, column));
+            let columns = shrink_number(column, 0)
+                .into_iter()
+                .map(|column| (row, column));
+            rows.chain(columns).collect()
+        },
+        |&(row, column)| {
+            let code = encode(row, column);
+            let seat: SeatCode = code.parse().map_err(|e| format!("{}: {}", code, e))?;
+            equal("row", i64::from(seat.find_row()), row)?;
+            equal("column", i64::from(seat.find_column()), column)?;
+            equal("seat_id", i64::from(seat.seat_id()), row * 8 + column)?;
+            equal(
+                "decode_seat_id",
+                i64::from(decode_seat_id(&code)),
+                row * 8 + column,
+            )
+        },
+    );
+}
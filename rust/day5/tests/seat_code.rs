@@ -0,0 +1,19 @@
+use day5::{find_free_seat, SeatCode};
+
+#[test]
+fn test_seat_code_decoding() {
+    let seat: SeatCode = "FBFBBFFRLR".parse().unwrap();
+    assert_eq!(seat.find_row(), 44);
+    assert_eq!(seat.find_column(), 5);
+    assert_eq!(seat.seat_id(), 357);
+
+    let last: SeatCode = "BBBBBBBRRR".parse().unwrap();
+    assert_eq!(last.find_row(), SeatCode::MAX_ROW);
+    assert_eq!(last.find_column(), SeatCode::MAX_COLUMN);
+}
+
+#[test]
+fn test_find_free_seat() {
+    assert_eq!(find_free_seat(&[10, 8, 11, 7]), Some(9));
+    assert_eq!(find_free_seat(&[7, 8, 9]), None);
+}
@@ -0,0 +1,127 @@
+//! Day 6: Custom Customs. Count the questions each group of passengers answered "yes" to.
+
+use common::error::{Location, ParseError};
+use common::records::records;
+use common::Solution;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The answers of a group, one line per person. Every character is a question the person
+/// answered "yes" to.
+pub struct GroupAnswers<'a> {
+    answers: Vec<&'a str>,
+}
+
+impl<'a> GroupAnswers<'a> {
+    /// The number of questions anyone in the group answered "yes" to.
+    pub fn unique_questions_answered(&self) -> i32 {
+        let mut unique_questions_answered: HashMap<char, bool> = HashMap::new();
+        self.answers.iter().for_each(|&member_answers| {
+            member_answers.chars().for_each(|c| {
+                unique_questions_answered.entry(c).or_insert(true);
+            })
+        });
+        unique_questions_answered.len() as i32
+    }
+
+    /// The number of questions everyone in the group answered "yes" to.
+    pub fn all_yes_question_count(&self) -> i32 {
+        let mut unique_questions_answered: HashMap<char, i32> = HashMap::new();
+        self.answers.iter().for_each(|&member_answers| {
+            member_answers.chars().for_each(|c| {
+                let counter = unique_questions_answered.entry(c).or_default();
+                *counter += 1
+            })
+        });
+        unique_questions_answered
+            .values()
+            .filter(|&&v| v == self.answers.len() as i32)
+            .count() as i32
+    }
+}
+
+impl<'a> TryFrom<Vec<&'a str>> for GroupAnswers<'a> {
+    type Error = ParseError;
+
+    /// Takes one line per person, ignoring the whitespace around it. Questions are `a` to `z`.
+    fn try_from(lines: Vec<&'a str>) -> Result<Self, Self::Error> {
+        let mut answers = Vec::with_capacity(lines.len());
+        for (number, line) in lines.into_iter().enumerate() {
+            let member_answers = line.trim();
+            if let Some((index, c)) = member_answers
+                .char_indices()
+                .find(|&(_, c)| !c.is_ascii_lowercase())
+            {
+                return Err(ParseError::Unexpected {
+                    at: Location::of(number + 1, line, &member_answers[index..]),
+                    text: c.to_string(),
+                    expected: "a question from `a` to `z`",
+                });
+            }
+            answers.push(member_answers);
+        }
+        Ok(GroupAnswers { answers })
+    }
+}
+
+/// Groups the lines of the input. Groups are separated by blank lines.
+pub fn parse_input_lines(input: &str) -> Result<Vec<GroupAnswers<'_>>, ParseError> {
+    records(input)
+        .map(|record| {
+            GroupAnswers::try_from(record.lines().collect::<Vec<_>>())
+                .map_err(|e| e.below(record.first_line - 1))
+        })
+        .collect()
+}
+
+impl<'x> Solution for GroupAnswers<'x> {
+    type Parsed<'a> = Vec<GroupAnswers<'a>>;
+    type Answer = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input_lines(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed
+            .iter()
+            .map(GroupAnswers::unique_questions_answered)
+            .sum()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer {
+        parsed
+            .iter()
+            .map(GroupAnswers::all_yes_question_count)
+            .sum()
+    }
+}
+
+#[test]
+fn test_group_answers_count() {
+    let input = include_str!("../examples/example.txt");
+    let parsed_lines = parse_input_lines(input).unwrap();
+    let total_unique_answers: i32 = parsed_lines
+        .iter()
+        .map(GroupAnswers::unique_questions_answered)
+        .sum();
+    assert_eq!(total_unique_answers, 11);
+
+    let part2: i32 = parsed_lines
+        .iter()
+        .map(GroupAnswers::all_yes_question_count)
+        .sum();
+    assert_eq!(part2, 6);
+}
+
+#[test]
+fn test_group_answers_parse_errors() {
+    assert_eq!(
+        GroupAnswers::parse("abc\n\na\n b1").err(),
+        Some(ParseError::Unexpected {
+            at: Location::new(4, 3),
+            text: "1".to_string(),
+            expected: "a question from `a` to `z`",
+        })
+    );
+}
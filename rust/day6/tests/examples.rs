@@ -0,0 +1,7 @@
+use day6::GroupAnswers;
+use std::path::Path;
+
+#[test]
+fn test_examples() {
+    common::examples::assert_examples::<GroupAnswers>(Path::new(env!("CARGO_MANIFEST_DIR")));
+}
@@ -0,0 +1,19 @@
+use common::fuzz::{assert_no_panics, example_corpus, FuzzConfig};
+use day6::{parse_input_lines, GroupAnswers};
+use std::convert::TryFrom;
+use std::path::Path;
+
+#[test]
+fn test_parsers_never_panic() {
+    let config = FuzzConfig::from_env();
+    let corpus = example_corpus(Path::new(env!("CARGO_MANIFEST_DIR")));
+    assert_no_panics("GroupAnswers::try_from", &config, &corpus, |input| {
+        if let Ok(group) = GroupAnswers::try_from(input.lines().collect::<Vec<_>>()) {
+            group.unique_questions_answered();
+            group.all_yes_question_count();
+        }
+    });
+    assert_no_panics("parse_input_lines", &config, &corpus, |input| {
+        let _ = parse_input_lines(input);
+    });
+}
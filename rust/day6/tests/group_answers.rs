@@ -0,0 +1,17 @@
+use day6::{parse_input_lines, GroupAnswers};
+use std::convert::TryFrom;
+
+#[test]
+fn test_group_answers() {
+    let group = GroupAnswers::try_from(vec!["ab", "ac"]).unwrap();
+    assert_eq!(group.unique_questions_answered(), 3);
+    assert_eq!(group.all_yes_question_count(), 1);
+}
+
+#[test]
+fn test_parse_input_lines() {
+    let groups = parse_input_lines("abc\n\n\r\na\r\nb \n").unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1].unique_questions_answered(), 2);
+    assert_eq!(groups[1].all_yes_question_count(), 0);
+}
@@ -0,0 +1,67 @@
+use common::fuzz::FuzzConfig;
+use common::property::{assert_property, equal, shrink_vec};
+use common::random::Rng;
+use day6::GroupAnswers;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// The answers of a group: each person answered "yes" to a few distinct questions. Questions
+/// come from a small set, so people agree on some of them.
+fn group(rng: &mut Rng) -> Vec<String> {
+    (0..rng.range(1, 5))
+        .map(|_| {
+            let mut questions: Vec<char> = "abcdefg".chars().collect();
+            rng.shuffle(&mut questions);
+            questions[..rng.range(1, 7) as usize].iter().collect()
+        })
+        .collect()
+}
+
+/// Simpler groups: fewer people, or a person with one question less.
+fn shrink(people: &[String]) -> Vec<Vec<String>> {
+    shrink_vec(people, |person| {
+        (0..person.len())
+            .filter(|_| person.len() > 1)
+            .map(|index| {
+                let mut fewer = person.clone();
+                fewer.remove(index);
+                fewer
+            })
+            .collect()
+    })
+    .into_iter()
+    .filter(|people| !people.is_empty())
+    .collect()
+}
+
+#[test]
+fn test_counts_match_set_operations() {
+    assert_property(
+        "group counts match set union and intersection",
+        &FuzzConfig::from_env(),
+        group,
+        |people| shrink(people),
+        |people| {
+            let group =
+                GroupAnswers::try_from(people.iter().map(String::as_str).collect::<Vec<_>>())
+                    .map_err(|e| e.to_string())?;
+            let unique = group.unique_questions_answered();
+            let all_yes = group.all_yes_question_count();
+            if unique < all_yes {
+                return Err(format!(
+                    "{} unique questions, but {} all yes",
+                    unique, all_yes
+                ));
+            }
+
+            let sets: Vec<BTreeSet<char>> = people.iter().map(|p| p.chars().collect()).collect();
+            let union: BTreeSet<char> = sets.iter().flatten().copied().collect();
+            let intersection = union
+                .iter()
+                .filter(|question| sets.iter().all(|set| set.contains(question)))
+                .count();
+            equal("unique_questions_answered", unique as usize, union.len())?;
+            equal("all_yes_question_count", all_yes as usize, intersection)
+        },
+    );
+}